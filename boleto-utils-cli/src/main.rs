@@ -1,7 +1,5 @@
 use anyhow::{Result, anyhow};
-use boleto_utils::arrecadacao::{CodBarras as CodBarrasArr, LinhaDigitavel as LinhaDigitavelArr};
-use boleto_utils::cobranca::{CodBarras as CodBarrasCob, LinhaDigitavel as LinhaDigitavelCob};
-use boleto_utils::{Boleto, BoletoError};
+use boleto_utils::Boleto;
 use clap::{Args, Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
@@ -51,7 +49,7 @@ fn main() -> Result<()> {
     match &cli.command {
         None => return Err(anyhow!("Comando não encontrado, use --help para ajuda.")),
         Some(Commands::Info(input)) => {
-            let boleto = Boleto::new(input.cod_barras.as_bytes())?;
+            let boleto: Boleto = input.cod_barras.parse()?;
 
             match input.format {
                 Format::Text => println!("{}", boleto),
@@ -60,98 +58,20 @@ fn main() -> Result<()> {
             }
         }
         Some(Commands::DigitoVerificador(input)) => {
-            let input = input.cod_barras.as_bytes();
-
-            match input.first() {
-                None => return Err(anyhow::Error::new(BoletoError::InvalidLength)),
-                Some(b'8') => {
-                    let (cod_barras, linha_digitavel) = match input.len() {
-                        44 => {
-                            let cod_barras = CodBarrasArr::new(input)?;
-                            let linha_digitavel: LinhaDigitavelArr = (&cod_barras).try_into()?;
-                            (cod_barras, linha_digitavel)
-                        },
-                        48 => {
-                            let linha_digitavel = LinhaDigitavelArr::new(input)?;
-                            let cod_barras: CodBarrasArr = (&linha_digitavel).into();
-                            (cod_barras, linha_digitavel)
-                        },
-                        _ => return Err(anyhow::Error::new(BoletoError::InvalidLength)),
-                    };
-                    let dv = cod_barras.calculate_dv();
-                    let dvs = cod_barras.calculate_dv_campos();
-
-                    // atualizando dv dos campos
-                    let mut cod_barras = *cod_barras;
-                    cod_barras[3] = dv + b'0';
-
-                    let mut linha_digitavel = *linha_digitavel;
-                    linha_digitavel[11] = dvs.0;
-                    linha_digitavel[23] = dvs.1;
-                    linha_digitavel[35] = dvs.2;
-                    linha_digitavel[47] = dvs.3;
-                    linha_digitavel[3] = dv + b'0';
-
-                    println!(
-                        concat!(
-                            "        DV geral: {}\n",
-                            "       DV campos: {} | {} | {} | {}\n",
-                            "Código de barras: {}\n",
-                            " Linha digitável: {}",
-                        ),
-                        dv,
-                        dvs.0 - b'0',
-                        dvs.1 - b'0',
-                        dvs.2 - b'0',
-                        dvs.3 - b'0',
-                        unsafe { std::str::from_utf8_unchecked(cod_barras.as_ref()) },
-                        unsafe { std::str::from_utf8_unchecked(linha_digitavel.as_ref()) },
-                    );
-                },
-                _ => {
-                    let (cod_barras, linha_digitavel) = match input.len() {
-                        44 => {
-                            let cod_barras = CodBarrasCob::new(input)?;
-                            let linha_digitavel: LinhaDigitavelCob = (&cod_barras).try_into()?;
-                            (cod_barras, linha_digitavel)
-                        },
-                        47 => {
-                            let linha_digitavel = LinhaDigitavelCob::new(input)?;
-                            let cod_barras: CodBarrasCob = (&linha_digitavel).into();
-                            (cod_barras, linha_digitavel)
-                        },
-                        _ => return Err(anyhow::Error::new(BoletoError::InvalidLength)),
-                    };
-
-                    let dv = cod_barras.calculate_dv();
-                    let dvs = cod_barras.calculate_dv_campos();
-
-                    // atualizando dv dos campos
-                    let mut cod_barras = *cod_barras;
-                    cod_barras[4] = dv + b'0';
-
-                    let mut linha_digitavel = *linha_digitavel;
-                    linha_digitavel[9] = dvs.0;
-                    linha_digitavel[20] = dvs.1;
-                    linha_digitavel[31] = dvs.2;
-                    linha_digitavel[32] = dv + b'0';
-
-                    println!(
-                        concat!(
-                            "        DV geral: {}\n",
-                            "       DV campos: {} | {} | {}\n",
-                            "Código de barras: {}\n",
-                            " Linha digitável: {}\n",
-                        ),
-                        dv,
-                        dvs.0 - b'0',
-                        dvs.1 - b'0',
-                        dvs.2 - b'0',
-                        unsafe { std::str::from_utf8_unchecked(cod_barras.as_ref()) },
-                        unsafe { std::str::from_utf8_unchecked(linha_digitavel.as_ref()) },
-                    );
-                },
-            }
+            let completado = Boleto::complete(input.cod_barras.as_bytes())?;
+
+            println!(
+                concat!(
+                    "        DV geral: {}\n",
+                    "       DV campos: {}\n",
+                    "Código de barras: {}\n",
+                    " Linha digitável: {}",
+                ),
+                completado.dv_geral(),
+                completado.dvs_campos().iter().map(u8::to_string).collect::<Vec<_>>().join(" | "),
+                completado.cod_barras(),
+                completado.linha_digitavel(),
+            );
         },
     }
 
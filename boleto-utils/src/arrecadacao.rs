@@ -1,21 +1,40 @@
-use std::convert::{From, TryFrom};
-use std::fmt;
-use std::str::from_utf8_unchecked;
-
-use serde::Serialize;
-
+use core::convert::{From, TryFrom};
+use core::fmt;
+use core::str::from_utf8_unchecked;
+
+#[cfg(feature = "serde")]
+use serde::de::value::MapAccessDeserializer;
+#[cfg(feature = "serde")]
+use serde::de::{self, MapAccess, Visitor};
+#[cfg(feature = "serde")]
+use serde::ser::SerializeMap;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+use crate::convenios;
+#[cfg(feature = "std")]
+use crate::diagnostico::{validar_estrutura, Campo, Diagnostic};
+#[cfg(feature = "std")]
+use crate::render::itf::{self, Itf};
+#[cfg(feature = "serde")]
+use crate::utils::serde_utils::{conferir, presente};
 use crate::utils::{self, dv_utils};
+use crate::valor::Valor;
 use crate::BoletoError;
 
-pub struct CodBarras([u8; Arrecadacao::COD_BARRAS_LENGTH]);
+#[derive(PartialEq, Eq)]
+pub struct CodBarras([u8; CodBarras::LENGTH]);
 
 impl CodBarras {
+    pub const LENGTH: usize = 44;
+
     pub fn new(input: &[u8]) -> Result<Self, BoletoError> {
         if input.first() != Some(&b'8') {
             return Err(BoletoError::InvalidArrecadacaoBarcode);
         }
 
-        if input.len() != Arrecadacao::COD_BARRAS_LENGTH {
+        if input.len() != Self::LENGTH {
             return Err(BoletoError::InvalidLength);
         }
 
@@ -26,14 +45,18 @@ impl CodBarras {
 
         TipoValor::try_from(input[2]).map_err(|_| BoletoError::InvalidTipoValor)?;
 
-        let mut cod_barras = [0u8; Arrecadacao::COD_BARRAS_LENGTH];
+        let mut cod_barras = [0u8; Self::LENGTH];
         cod_barras.copy_from_slice(input);
 
         Ok(Self(cod_barras))
     }
 
     pub fn as_str(&self) -> &str {
-        unsafe { std::str::from_utf8_unchecked(&self.0) }
+        unsafe { from_utf8_unchecked(&self.0) }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
     }
 
     pub fn tipo_valor(&self) -> Result<TipoValor, BoletoError> {
@@ -44,6 +67,75 @@ impl CodBarras {
         Segmento::try_from(self[1]).map_err(|_| BoletoError::InvalidSegmento)
     }
 
+    pub fn convenio(&self) -> Result<Convenio, BoletoError> {
+        Ok(match self.segmento()? {
+            Segmento::Carnes => {
+                let mut cadastro = [0u8; 8];
+                cadastro.clone_from_slice(&self[15..23]);
+                Convenio::Carne(cadastro)
+            },
+            _ => Convenio::Outros(utils::u8_array_to_u16(&self[15..19])),
+        })
+    }
+
+    /// Valor do boleto, apenas quando o tipo de valor indica que o campo
+    /// contém um valor em Reais
+    pub fn valor(&self) -> Option<Valor> {
+        match self.tipo_valor() {
+            Ok(TipoValor::ValorReaisMod10 | TipoValor::ValorReaisMod11) => {
+                match Valor::from_digits(&self[4..15]) {
+                    Ok(valor) if !valor.is_zero() => Some(valor),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Valida o tipo de valor, o segmento e o dígito verificador geral, sem
+    /// consultar os dados dos convênios
+    pub fn validate(&self) -> Result<(), BoletoError> {
+        self.tipo_valor()?;
+        self.segmento()?;
+
+        if self.calculate_dv() != self[3] - b'0' {
+            return Err(BoletoError::InvalidDigitoVerificadorGeral);
+        }
+
+        Ok(())
+    }
+
+    /// Lê um código de barras ou uma linha digitável, validando os dígitos
+    /// verificadores, o segmento e o tipo de valor (ver `validate`), mas não
+    /// o convênio. Disponível também sem `std`.
+    pub fn parse(value: &[u8]) -> Result<Self, BoletoError> {
+        if value.len() == LinhaDigitavel::LENGTH {
+            let linha_digitavel = LinhaDigitavel::new(value)?;
+            linha_digitavel.validate()?;
+
+            return Ok(Self::from(&linha_digitavel));
+        }
+
+        let cod_barras = Self::new(value)?;
+        cod_barras.validate()?;
+
+        Ok(cod_barras)
+    }
+
+    /// SVG do código de barras no tamanho físico (ver `render::svg`),
+    /// opcionalmente com a linha digitável formatada abaixo das barras
+    #[cfg(feature = "std")]
+    pub fn to_svg(&self, config: itf::Config, linha_digitavel: bool) -> Result<String, BoletoError> {
+        let itf = Itf::new(self, config)?;
+        let legenda = if linha_digitavel {
+            Some(LinhaDigitavel::try_from(self)?.formatted())
+        } else {
+            None
+        };
+
+        Ok(itf.to_svg(legenda.as_deref()))
+    }
+
     pub fn calculate_dv(&self) -> u8 {
         // Cria um iterator que itera sobre os caracteres do código de barras
         // exceto o dígito verificador
@@ -59,6 +151,10 @@ impl CodBarras {
         ) - b'0'
     }
 
+    pub fn update_dv(&mut self) {
+        self.0[3] = self.calculate_dv() + b'0';
+    }
+
     pub fn calculate_dv_campos(&self) -> (u8, u8, u8, u8) {
         if let TipoValor::QtdeMoedaMod10 | TipoValor::ValorReaisMod10 = self.tipo_valor().unwrap() {
             (
@@ -100,7 +196,7 @@ impl From<&LinhaDigitavel> for CodBarras {
 
         let LinhaDigitavel(src) = linha_digitavel;
 
-        let mut barcode = [0_u8; 44];
+        let mut barcode = [0_u8; CodBarras::LENGTH];
 
         barcode[0..11].copy_from_slice(&src[0..11]);
         barcode[11..22].copy_from_slice(&src[12..23]);
@@ -125,14 +221,15 @@ impl fmt::Display for CodBarras {
     }
 }
 
-impl std::ops::Deref for CodBarras {
-    type Target = [u8; Arrecadacao::COD_BARRAS_LENGTH];
+impl core::ops::Deref for CodBarras {
+    type Target = [u8; CodBarras::LENGTH];
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
+#[cfg(feature = "serde")]
 impl Serialize for CodBarras {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where
@@ -141,15 +238,29 @@ impl Serialize for CodBarras {
     }
 }
 
-pub struct LinhaDigitavel([u8; Arrecadacao::LINHA_DIGITAVEL_LENGTH]);
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CodBarras {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de> {
+        let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+        Self::new(value.as_bytes()).map_err(de::Error::custom)
+    }
+}
+
+#[derive(PartialEq, Eq)]
+pub struct LinhaDigitavel([u8; LinhaDigitavel::LENGTH]);
 
 impl LinhaDigitavel {
+    pub const LENGTH: usize = 48;
+
     pub fn new(input: &[u8]) -> Result<Self, BoletoError> {
         if input.first() != Some(&b'8') {
             return Err(BoletoError::InvalidArrecadacaoBarcode);
         }
 
-        if input.len() != Arrecadacao::LINHA_DIGITAVEL_LENGTH {
+        if input.len() != Self::LENGTH {
             return Err(BoletoError::InvalidLength);
         }
 
@@ -158,14 +269,36 @@ impl LinhaDigitavel {
             return Err(BoletoError::NumbersOnly);
         }
 
-        let mut linha_digitavel = [0u8; Arrecadacao::LINHA_DIGITAVEL_LENGTH];
+        let mut linha_digitavel = [0u8; Self::LENGTH];
         linha_digitavel.copy_from_slice(input);
 
         Ok(Self(linha_digitavel))
     }
 
     pub fn as_str(&self) -> &str {
-        unsafe { std::str::from_utf8_unchecked(&self.0) }
+        unsafe { from_utf8_unchecked(&self.0) }
+    }
+
+    /// Linha digitável no formato impresso no boleto:
+    /// `NNNNNNNNNNN-D NNNNNNNNNNN-D NNNNNNNNNNN-D NNNNNNNNNNN-D`
+    #[cfg(feature = "std")]
+    pub fn formatted(&self) -> String {
+        format!("{self:#}")
+    }
+
+    /// Valida os dígitos verificadores dos campos e o código de barras
+    /// correspondente (ver `CodBarras::validate`)
+    pub fn validate(&self) -> Result<(), BoletoError> {
+        let cod_barras = CodBarras::from(self);
+        cod_barras.validate()?;
+
+        let (dv1, dv2, dv3, dv4) = cod_barras.calculate_dv_campos();
+
+        if (self[11], self[23], self[35], self[47]) != (dv1, dv2, dv3, dv4) {
+            return Err(BoletoError::InvalidDigitoVerificadorCampos);
+        }
+
+        Ok(())
     }
 }
 
@@ -192,7 +325,7 @@ impl TryFrom<&CodBarras> for LinhaDigitavel {
         // ABCDEEEEEEE-W EEEEFFFFFFF-X FGGGGGGGGGG-Y GGGGGGGGGGG-Z
 
         let CodBarras(src) = cod_barras;
-        let mut digitable_line = [0_u8; 48];
+        let mut digitable_line = [0_u8; LinhaDigitavel::LENGTH];
         let (dv1, dv2, dv3, dv4) = cod_barras.calculate_dv_campos();
 
         // Campo 1
@@ -223,20 +356,35 @@ impl fmt::Debug for LinhaDigitavel {
     }
 }
 
+/// Com `{:#}` usa o formato impresso no boleto (ver `formatted`)
 impl fmt::Display for LinhaDigitavel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(self.as_str())
+        if !f.alternate() {
+            return f.write_str(self.as_str());
+        }
+
+        for (i, campo) in self.as_str().as_bytes().chunks(12).enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+
+            let campo = unsafe { from_utf8_unchecked(campo) };
+            write!(f, "{}-{}", &campo[..11], &campo[11..])?;
+        }
+
+        Ok(())
     }
 }
 
-impl std::ops::Deref for LinhaDigitavel {
-    type Target = [u8; Arrecadacao::LINHA_DIGITAVEL_LENGTH];
+impl core::ops::Deref for LinhaDigitavel {
+    type Target = [u8; LinhaDigitavel::LENGTH];
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
+#[cfg(feature = "serde")]
 impl Serialize for LinhaDigitavel {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where
@@ -245,7 +393,31 @@ impl Serialize for LinhaDigitavel {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for LinhaDigitavel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de> {
+        let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+        Self::new(value.as_bytes()).map_err(de::Error::custom)
+    }
+}
+
+/// Serializa a linha digitável junto com a versão formatada, para ser usado
+/// com `#[serde(flatten)]`
+#[cfg(feature = "serde")]
+fn serialize_linha_digitavel<S>(linha_digitavel: &LinhaDigitavel, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer {
+    let mut map = serializer.serialize_map(Some(2))?;
+    map.serialize_entry("linha_digitavel", linha_digitavel.as_str())?;
+    map.serialize_entry("linha_digitavel_formatada", &linha_digitavel.formatted())?;
+    map.end()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Segmento {
     Prefeituras,
     Saneamento,
@@ -275,6 +447,21 @@ impl TryFrom<u8> for Segmento {
     }
 }
 
+impl From<&Segmento> for u8 {
+    fn from(segmento: &Segmento) -> u8 {
+        match segmento {
+            Segmento::Prefeituras => b'1',
+            Segmento::Saneamento => b'2',
+            Segmento::EnergiaEletricaEGas => b'3',
+            Segmento::Telecomunicacoes => b'4',
+            Segmento::OrgaosGovernamentais => b'5',
+            Segmento::Carnes => b'6',
+            Segmento::MultasTransito => b'7',
+            Segmento::ExclusivoDoBanco => b'9',
+        }
+    }
+}
+
 impl fmt::Display for Segmento {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(match self {
@@ -290,7 +477,8 @@ impl fmt::Display for Segmento {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TipoValor {
     ValorReaisMod10,
     QtdeMoedaMod10,
@@ -312,7 +500,19 @@ impl TryFrom<u8> for TipoValor {
     }
 }
 
-#[derive(Debug, Serialize)]
+impl From<&TipoValor> for u8 {
+    fn from(tipo_valor: &TipoValor) -> u8 {
+        match tipo_valor {
+            TipoValor::ValorReaisMod10 => b'6',
+            TipoValor::QtdeMoedaMod10 => b'7',
+            TipoValor::ValorReaisMod11 => b'8',
+            TipoValor::QtdeMoedaMod11 => b'9',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Convenio {
     Carne([u8; 8]),
     Outros(u16),
@@ -327,18 +527,22 @@ impl fmt::Display for Convenio {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Arrecadacao {
     pub cod_barras: CodBarras,
+    #[cfg_attr(feature = "serde", serde(flatten, serialize_with = "serialize_linha_digitavel"))]
     pub linha_digitavel: LinhaDigitavel,
     pub segmento: Segmento,
     pub tipo_valor: TipoValor,
-    #[serde(skip)]
     pub digito_verificador: u8,
-    pub valor: Option<f64>,
+    pub valor: Option<Valor>,
     pub convenio: Convenio,
+    pub nome_convenio: Option<&'static str>,
 }
 
+#[cfg(feature = "std")]
 impl fmt::Display for Arrecadacao {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -354,94 +558,181 @@ impl fmt::Display for Arrecadacao {
             self.cod_barras,
             self.linha_digitavel,
             self.segmento,
-            self.convenio,
+            match self.nome_convenio {
+                Some(nome) => format!("[{}] {}", self.convenio, nome),
+                None => format!("{}", self.convenio),
+            },
             match self.valor {
-                Some(v) =>format!("{:.2}", v),
+                Some(v) => format!("{v}"),
                 None => "Sem valor informado".to_owned()
             },
         )
     }
 }
+/// Forma serializada de `Arrecadacao`. Apenas o código de barras é
+/// obrigatório, os demais campos são conferidos com os calculados a partir
+/// dele.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ArrecadacaoSerializada {
+    cod_barras: CodBarras,
+    linha_digitavel: Option<LinhaDigitavel>,
+    linha_digitavel_formatada: Option<String>,
+    segmento: Option<Segmento>,
+    tipo_valor: Option<TipoValor>,
+    digito_verificador: Option<u8>,
+    #[serde(default, deserialize_with = "presente")]
+    valor: Option<Option<Valor>>,
+    convenio: Option<Convenio>,
+    #[serde(default, deserialize_with = "presente")]
+    nome_convenio: Option<Option<String>>,
+}
+
+#[cfg(feature = "serde")]
+impl ArrecadacaoSerializada {
+    fn validar<E: de::Error>(self) -> Result<Arrecadacao, E> {
+        let arrecadacao = Arrecadacao::new(self.cod_barras.as_bytes()).map_err(E::custom)?;
+
+        conferir("linha_digitavel", self.linha_digitavel.as_ref(), &arrecadacao.linha_digitavel)?;
+        conferir("linha_digitavel_formatada", self.linha_digitavel_formatada, arrecadacao.linha_digitavel.formatted())?;
+        conferir("segmento", self.segmento, arrecadacao.segmento)?;
+        conferir("tipo_valor", self.tipo_valor, arrecadacao.tipo_valor)?;
+        conferir("digito_verificador", self.digito_verificador, arrecadacao.digito_verificador)?;
+        conferir("valor", self.valor, arrecadacao.valor)?;
+        conferir("convenio", self.convenio, arrecadacao.convenio)?;
+        conferir("nome_convenio", self.nome_convenio.as_ref().map(Option::as_deref), arrecadacao.nome_convenio)?;
+
+        Ok(arrecadacao)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct ArrecadacaoVisitor;
 
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for ArrecadacaoVisitor {
+    type Value = Arrecadacao;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("código de barras, linha digitável ou boleto de arrecadação serializado")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Arrecadacao::new(value.as_bytes()).map_err(E::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        ArrecadacaoSerializada::deserialize(MapAccessDeserializer::new(map))?.validar()
+    }
+}
+
+/// Aceita o código de barras, a linha digitável ou a forma serializada, que é
+/// validada novamente a partir do código de barras
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Arrecadacao {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de> {
+        deserializer.deserialize_any(ArrecadacaoVisitor)
+    }
+}
+
+
+#[cfg(feature = "std")]
 impl Arrecadacao {
-    pub const COD_BARRAS_LENGTH: usize = 44;
-    pub const LINHA_DIGITAVEL_LENGTH: usize = 48;
+    pub const COD_BARRAS_LENGTH: usize = CodBarras::LENGTH;
+    pub const LINHA_DIGITAVEL_LENGTH: usize = LinhaDigitavel::LENGTH;
 
     pub fn new(value: &[u8]) -> Result<Self, BoletoError> {
-        let (cod_barras, linha_digitavel): (CodBarras, LinhaDigitavel) = match value.len() {
-            Self::COD_BARRAS_LENGTH => {
-                let cod_barras = CodBarras::new(value)?;
-                let linha_digitavel = LinhaDigitavel::try_from(&cod_barras)?;
-                (cod_barras, linha_digitavel)
-            }
-            Self::LINHA_DIGITAVEL_LENGTH => {
-                let linha_digitavel = LinhaDigitavel::new(value)?;
-                ((&linha_digitavel).into(), linha_digitavel)
-            }
-            _ => return Err(BoletoError::InvalidLength),
-        };
+        Self::diagnose(value).map_err(|mut diagnostics| diagnostics.remove(0).erro)
+    }
 
-        let tipo_valor = cod_barras.tipo_valor()?;
+    /// Assim como `new`, mas retorna todos os problemas encontrados em vez de
+    /// apenas o primeiro
+    pub fn diagnose(value: &[u8]) -> Result<Self, Vec<Diagnostic>> {
+        validar_estrutura(value, &[Self::COD_BARRAS_LENGTH, Self::LINHA_DIGITAVEL_LENGTH], true)?;
 
-        let segmento: Segmento = cod_barras.segmento()?;
+        let is_linha_digitavel = value.len() == Self::LINHA_DIGITAVEL_LENGTH;
 
-        let convenio = match segmento {
-            Segmento::Carnes => {
-                let mut cadastro = [0u8; 8];
-                cadastro.clone_from_slice(&cod_barras[15..23]);
-                Convenio::Carne(cadastro)
-            },
-            _ => Convenio::Outros(utils::u8_array_to_u16(&cod_barras[15..19])),
+        // O tipo de valor é validado abaixo junto com os demais campos
+        let cod_barras = if is_linha_digitavel {
+            CodBarras::from(&LinhaDigitavel::new(value).expect("estrutura já validada"))
+        } else {
+            let mut cod_barras = [0u8; Self::COD_BARRAS_LENGTH];
+            cod_barras.copy_from_slice(value);
+            CodBarras(cod_barras)
         };
 
-        let digito_verificador = {
-            let dv = cod_barras.calculate_dv();
+        let mut diagnostics = Vec::new();
 
-            if dv != cod_barras[3] - b'0' {
-                return Err(BoletoError::InvalidDigitoVerificadorGeral);
-            }
-            dv
+        let tipo_valor = cod_barras.tipo_valor().map_err(|erro| {
+            diagnostics.push(Diagnostic::new(Campo::TipoValor, value, 2..3, erro));
+        });
+
+        let segmento = cod_barras.segmento().map_err(|erro| {
+            diagnostics.push(Diagnostic::new(Campo::Segmento, value, 1..2, erro));
+        });
+
+        // Sem o tipo de valor não é possível saber qual módulo usar nos DVs
+        let (Ok(tipo_valor), Ok(segmento)) = (tipo_valor, segmento) else {
+            return Err(diagnostics);
         };
 
-        {
-            let correct_dvs = cod_barras.calculate_dv_campos();
+        let convenio = cod_barras.convenio().expect("segmento já validado");
+
+        let nome_convenio = convenios::get(&segmento, &convenio);
 
-            if linha_digitavel[11] != correct_dvs.0
-                || linha_digitavel[23] != correct_dvs.1
-                || linha_digitavel[35] != correct_dvs.2
-                || linha_digitavel[47] != correct_dvs.3
-            {
-                return Err(BoletoError::InvalidDigitoVerificadorCampos);
+        let digito_verificador = cod_barras.calculate_dv();
+
+        if digito_verificador != cod_barras[3] - b'0' {
+            diagnostics.push(Diagnostic::dv(
+                Campo::DvGeral,
+                3..4,
+                digito_verificador + b'0',
+                cod_barras[3],
+                BoletoError::InvalidDigitoVerificadorGeral,
+            ));
+        }
+
+        let linha_digitavel = LinhaDigitavel::try_from(&cod_barras).expect("tipo de valor já validado");
+
+        if is_linha_digitavel {
+            // Os DVs dos campos calculados estão em `linha_digitavel`, os
+            // informados estão na entrada
+            for i in 0..4 {
+                let pos_dv = i * 12 + 11;
+
+                if value[pos_dv] != linha_digitavel[pos_dv] {
+                    diagnostics.push(Diagnostic::dv(
+                        Campo::linha_digitavel(i),
+                        i * 12..pos_dv + 1,
+                        linha_digitavel[pos_dv],
+                        value[pos_dv],
+                        BoletoError::InvalidDigitoVerificadorCampos,
+                    ));
+                }
             }
-        };
+        }
+
+        if !diagnostics.is_empty() {
+            return Err(diagnostics);
+        }
 
         Ok(Self {
-            valor: Self::valor(&cod_barras, &tipo_valor),
-            cod_barras,
+            valor: cod_barras.valor(),
             linha_digitavel,
+            cod_barras,
             segmento,
             tipo_valor,
             digito_verificador,
             convenio,
+            nome_convenio,
         })
     }
-
-    fn valor(barcode: &CodBarras, tipo: &TipoValor) -> Option<f64> {
-        match tipo {
-            TipoValor::ValorReaisMod10 | TipoValor::ValorReaisMod11 => {
-                let x = unsafe { std::str::from_utf8_unchecked(&barcode[4..15]) };
-                match x.parse::<f64>().unwrap() {
-                    x if x.is_normal() => Some(x / 100.00),
-                    _ => None,
-                }
-            }
-            _ => None,
-        }
-    }
 }
 
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
@@ -492,11 +783,12 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::redundant_pattern_matching)]
     fn get_valor_correctly() {
         assert!(matches!(Arrecadacao::new(b"86670000000000066667777777777777777777777777").unwrap().valor, None));
 
         let cases = [
-            (b"86625555555555566667777777777777777777777777", 5_555_555_555_u64),
+            (b"86625555555555566667777777777777777777777777", 55_555_555_555_u64),
             (b"86689999999999966667777777777777777777777777", 99_999_999_999_u64),
             (b"86651000000000166667777777777777777777777777", 10_000_000_001_u64),
             (b"86660000000000166667777777777777777777777777", 1_u64),
@@ -504,11 +796,10 @@ mod tests {
             (b"86691234567890166667777777777777777777777777", 12_345_678_901_u64),
         ];
 
-        for (barcode, _expected) in cases.iter() {
-            let valor_float = Arrecadacao::new(*barcode).unwrap().valor.unwrap();
-            let valor: u64 = (valor_float * 100.0).round() as u64;
+        for (barcode, expected) in cases.iter() {
+            let valor = Arrecadacao::new(*barcode).unwrap().valor.unwrap();
 
-            assert!(matches!(valor, _expected));
+            assert_eq!(valor.centavos(), *expected);
         }
     }
 
@@ -535,6 +826,19 @@ mod tests {
         assert!(matches!(Arrecadacao::new(b"896955555553555566667773777777777775777777777775").unwrap().convenio, Convenio::Outros(_)));
     }
 
+    #[test]
+    fn get_nome_convenio_correctly() {
+        let arrecadacao = Arrecadacao::new(b"82690000000000013460000000000000000000000000").unwrap();
+
+        assert_eq!(arrecadacao.nome_convenio, Some("BRK AMBIENTAL ARAGUAIA SANEAMENTO SA"));
+        assert!(arrecadacao.to_string().contains("Convênio: [1346] BRK AMBIENTAL ARAGUAIA SANEAMENTO SA"));
+
+        let arrecadacao = Arrecadacao::new(b"84645555555555566667777777777777777777777777").unwrap();
+
+        assert_eq!(arrecadacao.nome_convenio, None);
+        assert!(arrecadacao.to_string().contains("Convênio: 6666\n"));
+    }
+
     #[test]
     fn validate_digito_verificador_correctly() {
         let barcodes = [
@@ -552,6 +856,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn format_linha_digitavel_correctly() {
+        let linha_digitavel = LinhaDigitavel::new(b"836555555553555566667773777777777775777777777775").unwrap();
+        let expected = "83655555555-3 55556666777-3 77777777777-5 77777777777-5";
+
+        assert_eq!(linha_digitavel.formatted(), expected);
+        assert_eq!(format!("{linha_digitavel:#}"), expected);
+        assert_eq!(format!("{linha_digitavel}"), "836555555553555566667773777777777775777777777775");
+    }
+
     #[test]
     fn validate_convert_barcode_to_linha_digitavel() {
         let barcodes = [
@@ -595,4 +909,18 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn to_svg_with_linha_digitavel() {
+        let cod_barras = CodBarras::new(b"81675555555555566667777777777777777777777777").unwrap();
+        let linha_digitavel = LinhaDigitavel::try_from(&cod_barras).unwrap().formatted();
+
+        let svg = cod_barras.to_svg(itf::Config::default(), false).unwrap();
+        assert!(svg.contains(r#"width="112.87mm" height="13mm""#));
+        assert!(!svg.contains(&linha_digitavel));
+
+        let svg = cod_barras.to_svg(itf::Config::default(), true).unwrap();
+        assert!(svg.contains(r#"width="112.87mm" height="19mm""#));
+        assert!(svg.contains(&format!(">{linha_digitavel}</text>")));
+    }
 }
@@ -0,0 +1,101 @@
+use std::fmt;
+
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::cobranca::CodBanco;
+
+const INSTITUICOES_BANCARIAS_CSV: &str = include_str!("../data/instituicoes-bancarias.csv");
+
+lazy_static! {
+    /// Instituições bancárias conhecidas, ordenadas pelo código de compensação
+    static ref BANCOS: Vec<Banco> = {
+        let mut reader = csv::Reader::from_reader(INSTITUICOES_BANCARIAS_CSV.as_bytes());
+
+        let mut bancos: Vec<Banco> = reader
+            .records()
+            .map(|record| {
+                let record = record.expect("data/instituicoes-bancarias.csv deve ser um CSV válido");
+
+                Banco {
+                    id: record[0].parse().expect("código do banco deve ser numérico"),
+                    nome: record[1].to_owned(),
+                    cnpj_base: record[2].to_owned(),
+                }
+            })
+            .collect();
+
+        bancos.sort_by_key(|banco| banco.id);
+        bancos
+    };
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Banco {
+    pub id: u16,
+    pub nome: String,
+    pub cnpj_base: String,
+}
+
+impl fmt::Display for Banco {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:03}] {}", self.id, self.nome)
+    }
+}
+
+/// Busca a instituição bancária pelo código de compensação
+pub fn get(cod_banco: CodBanco) -> Option<&'static Banco> {
+    BANCOS
+        .binary_search_by_key(&cod_banco.0, |banco| banco.id)
+        .ok()
+        .map(|i| &BANCOS[i])
+}
+
+/// Itera sobre todas as instituições bancárias conhecidas, ordenadas pelo código
+pub fn iter() -> impl Iterator<Item = &'static Banco> {
+    BANCOS.iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_banco_correctly() {
+        let cases = [
+            (1_u16, "Banco do Brasil S.A.", "00000000"),
+            (104, "Caixa Econômica Federal", "00360305"),
+            (301, "BPP Instituição de Pagamento S.A.", "13370835"),
+            (15, "UBS Brasil Corretora de Câmbio, Títulos e Valores Mobiliários S.A.", "02819125"),
+        ];
+
+        for (id, nome, cnpj_base) in cases {
+            let banco = get(CodBanco(id)).unwrap();
+
+            assert_eq!(banco.id, id);
+            assert_eq!(banco.nome, nome);
+            assert_eq!(banco.cnpj_base, cnpj_base);
+        }
+
+        assert_eq!(get(CodBanco(0)), None);
+        assert_eq!(get(CodBanco(999)), None);
+    }
+
+    #[test]
+    fn iter_bancos_sorted() {
+        let ids: Vec<u16> = iter().map(|banco| banco.id).collect();
+
+        assert_eq!(ids.len(), 253);
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn display_banco_correctly() {
+        assert_eq!(
+            get(CodBanco(301)).unwrap().to_string(),
+            "[301] BPP Instituição de Pagamento S.A.",
+        );
+    }
+}
@@ -1,10 +1,71 @@
-use crate::{cobranca::{Cobranca, CodBarras, CodigoMoeda, CodBanco}, utils::date_to_fator_vencimento};
-use chrono::NaiveDate;
+use crate::{cobranca::{CodBarras, CodigoMoeda, CodBanco}, valor::Valor};
+use crate::fator_vencimento::{date_to_fator_vencimento, fator_vencimento_to_date, Data};
+use crate::arrecadacao::{CodBarras as CodBarrasArr, Convenio, Segmento, TipoValor};
+use crate::utils::escrever_digitos;
+use crate::BoletoError;
+#[cfg(feature = "std")]
+use crate::{arrecadacao::Arrecadacao, cobranca::Cobranca};
+#[cfg(feature = "std")]
+use crate::campo_livre::CampoLivreEncoder;
 
+/// Dígitos informados para o campo livre, guardados sem alocação. A
+/// quantidade informada é mantida para que entradas maiores que `N` sejam
+/// rejeitadas no `build()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigitosCampoLivre<const N: usize> {
+    digitos: [u8; N],
+    tamanho: usize,
+}
+
+impl<const N: usize> DigitosCampoLivre<N> {
+    pub fn new(digitos: &[u8]) -> Self {
+        let mut buffer = [b'0'; N];
+        let copiados = digitos.len().min(N);
+        buffer[..copiados].copy_from_slice(&digitos[..copiados]);
+
+        Self { digitos: buffer, tamanho: digitos.len() }
+    }
+
+    /// Quantidade de dígitos informada
+    pub fn len(&self) -> usize {
+        self.tamanho
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tamanho == 0
+    }
+
+    /// Dígitos informados, ou `None` se couberem mais que `N` ou houver
+    /// caracteres que não são dígitos
+    pub fn digitos(&self) -> Option<&[u8]> {
+        self.digitos
+            .get(..self.tamanho)
+            .filter(|digitos| digitos.iter().all(u8::is_ascii_digit))
+    }
+}
+
+impl<const N: usize> Default for DigitosCampoLivre<N> {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+#[cfg(feature = "std")]
 impl Cobranca {
     pub fn builder() -> CobrancaBuilder<NoCodBanco, NoCodMoeda> {
         CobrancaBuilder::new()
     }
+
+    /// Builder preenchido com os dados deste boleto, incluindo o campo livre
+    pub fn to_builder(&self) -> CobrancaBuilder<CodBanco, CodigoMoeda> {
+        CobrancaBuilder {
+            cod_banco: self.cod_banco,
+            cod_moeda: self.cod_moeda,
+            data_vencimento: self.data_vencimento,
+            valor: self.valor,
+            campo_livre: Some(DigitosCampoLivre::new(self.cod_barras.campo_livre())),
+        }
+    }
 }
 
 pub struct NoCodMoeda;
@@ -14,8 +75,9 @@ pub struct NoCodBanco;
 pub struct CobrancaBuilder<CB, CM> {
     pub cod_banco: CB,
     pub cod_moeda: CM,
-    pub data_vencimento: Option<NaiveDate>,
-    pub valor: Option<f64>,
+    pub data_vencimento: Option<Data>,
+    pub valor: Option<Valor>,
+    pub campo_livre: Option<DigitosCampoLivre<25>>,
 }
 
 impl CobrancaBuilder<NoCodBanco, NoCodMoeda> {
@@ -25,17 +87,25 @@ impl CobrancaBuilder<NoCodBanco, NoCodMoeda> {
             cod_moeda: NoCodMoeda,
             data_vencimento: None,
             valor: None,
+            campo_livre: None,
         }
     }
 }
 
+impl Default for CobrancaBuilder<NoCodBanco, NoCodMoeda> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<CM> CobrancaBuilder<NoCodBanco, CM> {
     pub fn cod_banco(self, cod_banco: CodBanco) -> CobrancaBuilder<CodBanco, CM> {
         CobrancaBuilder {
-            cod_banco: cod_banco,
+            cod_banco,
             cod_moeda: self.cod_moeda,
             data_vencimento: self.data_vencimento,
             valor: self.valor,
+            campo_livre: self.campo_livre,
         }
     }
 }
@@ -44,63 +114,326 @@ impl<CB> CobrancaBuilder<CB, NoCodMoeda> {
     pub fn cod_moeda(self, cod_moeda: CodigoMoeda) -> CobrancaBuilder<CB, CodigoMoeda> {
         CobrancaBuilder {
             cod_banco: self.cod_banco,
-            cod_moeda: cod_moeda,
+            cod_moeda,
             data_vencimento: self.data_vencimento,
             valor: self.valor,
+            campo_livre: self.campo_livre,
         }
     }
 }
 
 impl<CB, CM> CobrancaBuilder<CB, CM> {
-    pub fn data_vencimento(self, data_vencimento: NaiveDate) -> CobrancaBuilder<CB, CM> {
+    pub fn data_vencimento(self, data_vencimento: Data) -> CobrancaBuilder<CB, CM> {
         CobrancaBuilder {
             cod_banco: self.cod_banco,
             cod_moeda: self.cod_moeda,
             data_vencimento: Some(data_vencimento),
             valor: self.valor,
+            campo_livre: self.campo_livre,
         }
     }
 }
 
 impl<CB, CM> CobrancaBuilder<CB, CM> {
-    pub fn valor(self, valor: f64) -> CobrancaBuilder<CB, CM> {
+    pub fn valor(self, valor: Valor) -> CobrancaBuilder<CB, CM> {
         CobrancaBuilder {
             cod_banco: self.cod_banco,
             cod_moeda: self.cod_moeda,
             data_vencimento: self.data_vencimento,
             valor: Some(valor),
+            campo_livre: self.campo_livre,
+        }
+    }
+}
+
+impl<CB, CM> CobrancaBuilder<CB, CM> {
+    /// Campo livre (posições 20 a 44) com 25 dígitos, validado no `build()`
+    pub fn campo_livre(self, campo_livre: &[u8]) -> CobrancaBuilder<CB, CM> {
+        CobrancaBuilder {
+            campo_livre: Some(DigitosCampoLivre::new(campo_livre)),
+            ..self
         }
     }
+
+    /// Campo livre gerado pelo codificador de um banco
+    #[cfg(feature = "std")]
+    pub fn encode_campo_livre<E: CampoLivreEncoder>(self, encoder: &E) -> Result<CobrancaBuilder<CB, CM>, BoletoError> {
+        Ok(self.campo_livre(&encoder.encode()?))
+    }
 }
 
 impl CobrancaBuilder<CodBanco, CodigoMoeda> {
-    pub fn build(self) -> Cobranca {
-        let cobranca = {
-            let mut result = [b'0'; 44];
-
-            let fator_vencimento = if let Some(data_vencimento) = self.data_vencimento {
-                date_to_fator_vencimento(data_vencimento).unwrap_or(0u16)
-            } else {
-                0u16
-            };
-
-            let valor = if let Some(valor) = self.valor {
-                (valor * 100.0).trunc() as u64
-            } else {
-                0u64
-            };
-
-            result[0..3].copy_from_slice(format!("{:03}", self.cod_banco.0).as_ref());
-            result[3] = self.cod_moeda.into();
-            result[5..9].copy_from_slice(format!("{:04}", fator_vencimento).as_ref());
-            result[9..19].copy_from_slice(format!("{:010}", valor).as_ref());
-
-            let mut cobranca = CodBarras::new(&result).unwrap();
-            cobranca.update_dv();
-
-            cobranca
+    /// Monta apenas o código de barras, com o dígito verificador calculado,
+    /// sem validar o campo livre com os dados do banco
+    pub fn build_cod_barras(&self) -> Result<CodBarras, BoletoError> {
+        let mut result = [b'0'; CodBarras::LENGTH];
+
+        if self.cod_banco.0 > 999 {
+            return Err(BoletoError::InvalidCodBanco);
+        }
+
+        // Só são aceitas datas que a leitura do código de barras resolve de
+        // volta para a mesma data (de 01/01/2010 a 22/08/2034)
+        let fator_vencimento = match self.data_vencimento {
+            Some(data_vencimento) => {
+                let fator = date_to_fator_vencimento(data_vencimento)?;
+
+                if fator_vencimento_to_date(fator) != Some(data_vencimento) {
+                    return Err(BoletoError::InvalidDataVencimento);
+                }
+
+                fator
+            },
+            None => 0u16,
         };
 
-        Cobranca::new(cobranca.as_bytes()).unwrap()
+        let valor = self.valor.unwrap_or(Valor::ZERO).centavos();
+        if valor > 9_999_999_999 {
+            return Err(BoletoError::ValorOverflow);
+        }
+
+        escrever_digitos(&mut result[0..3], self.cod_banco.0.into());
+        result[3] = self.cod_moeda.into();
+        escrever_digitos(&mut result[5..9], fator_vencimento.into());
+        escrever_digitos(&mut result[9..19], valor);
+
+        if let Some(campo_livre) = &self.campo_livre {
+            match campo_livre.digitos() {
+                Some(digitos) if digitos.len() == 25 => result[19..44].copy_from_slice(digitos),
+                _ => return Err(BoletoError::InvalidCampoLivre),
+            }
+        }
+
+        let mut cod_barras = CodBarras::new(&result)?;
+        cod_barras.update_dv();
+
+        Ok(cod_barras)
+    }
+
+    /// Valida apenas o que o builder controla (banco, data, valor, tamanho do
+    /// campo livre e DVs). O campo livre é decodificado como em
+    /// `Cobranca::new`: um campo livre fora do layout do banco, inclusive o
+    /// zerado usado quando nenhum é informado, fica em `campo_livre_error`.
+    #[cfg(feature = "std")]
+    pub fn build(self) -> Result<Cobranca, BoletoError> {
+        Cobranca::new(self.build_cod_barras()?.as_bytes())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Arrecadacao {
+    pub fn builder() -> ArrecadacaoBuilder<NoSegmento, NoTipoValor, NoConvenio> {
+        ArrecadacaoBuilder::new()
+    }
+
+    /// Builder preenchido com os dados deste boleto, incluindo o campo livre.
+    ///
+    /// O valor é copiado diretamente do código de barras, preservando também
+    /// a quantidade de moeda quando o tipo de valor não for em Reais.
+    pub fn to_builder(&self) -> ArrecadacaoBuilder<Segmento, TipoValor, Convenio> {
+        let inicio_campo_livre = match self.convenio {
+            Convenio::Carne(_) => 23,
+            Convenio::Outros(_) => 19,
+        };
+
+        ArrecadacaoBuilder {
+            segmento: self.segmento,
+            tipo_valor: self.tipo_valor,
+            convenio: self.convenio,
+            valor: Valor::from_digits(&self.cod_barras[4..15]).ok(),
+            campo_livre: DigitosCampoLivre::new(&self.cod_barras[inicio_campo_livre..]),
+        }
+    }
+}
+
+pub struct NoSegmento;
+pub struct NoTipoValor;
+pub struct NoConvenio;
+
+pub struct ArrecadacaoBuilder<S, T, C> {
+    pub segmento: S,
+    pub tipo_valor: T,
+    pub convenio: C,
+    pub valor: Option<Valor>,
+    pub campo_livre: DigitosCampoLivre<25>,
+}
+
+impl ArrecadacaoBuilder<NoSegmento, NoTipoValor, NoConvenio> {
+    pub fn new() -> ArrecadacaoBuilder<NoSegmento, NoTipoValor, NoConvenio> {
+        Self {
+            segmento: NoSegmento,
+            tipo_valor: NoTipoValor,
+            convenio: NoConvenio,
+            valor: None,
+            campo_livre: DigitosCampoLivre::default(),
+        }
+    }
+}
+
+impl Default for ArrecadacaoBuilder<NoSegmento, NoTipoValor, NoConvenio> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> ArrecadacaoBuilder<NoSegmento, T, C> {
+    pub fn segmento(self, segmento: Segmento) -> ArrecadacaoBuilder<Segmento, T, C> {
+        ArrecadacaoBuilder {
+            segmento,
+            tipo_valor: self.tipo_valor,
+            convenio: self.convenio,
+            valor: self.valor,
+            campo_livre: self.campo_livre,
+        }
+    }
+}
+
+impl<S, C> ArrecadacaoBuilder<S, NoTipoValor, C> {
+    pub fn tipo_valor(self, tipo_valor: TipoValor) -> ArrecadacaoBuilder<S, TipoValor, C> {
+        ArrecadacaoBuilder {
+            segmento: self.segmento,
+            tipo_valor,
+            convenio: self.convenio,
+            valor: self.valor,
+            campo_livre: self.campo_livre,
+        }
+    }
+}
+
+impl<S, T> ArrecadacaoBuilder<S, T, NoConvenio> {
+    /// Convênio da empresa/órgão: `Convenio::Carne` (8 primeiros dígitos do
+    /// CNPJ) para o segmento de carnês e `Convenio::Outros` (4 dígitos) para
+    /// os demais segmentos
+    pub fn convenio(self, convenio: Convenio) -> ArrecadacaoBuilder<S, T, Convenio> {
+        ArrecadacaoBuilder {
+            segmento: self.segmento,
+            tipo_valor: self.tipo_valor,
+            convenio,
+            valor: self.valor,
+            campo_livre: self.campo_livre,
+        }
+    }
+}
+
+impl<S, T, C> ArrecadacaoBuilder<S, T, C> {
+    pub fn valor(self, valor: Valor) -> ArrecadacaoBuilder<S, T, C> {
+        ArrecadacaoBuilder {
+            valor: Some(valor),
+            ..self
+        }
+    }
+
+    /// Campo livre da empresa/órgão, completado com zeros à esquerda
+    pub fn campo_livre(self, campo_livre: &[u8]) -> ArrecadacaoBuilder<S, T, C> {
+        ArrecadacaoBuilder {
+            campo_livre: DigitosCampoLivre::new(campo_livre),
+            ..self
+        }
+    }
+}
+
+impl ArrecadacaoBuilder<Segmento, TipoValor, Convenio> {
+    /// Monta apenas o código de barras, com o dígito verificador calculado
+    pub fn build_cod_barras(&self) -> Result<CodBarrasArr, BoletoError> {
+        let mut result = [b'0'; CodBarrasArr::LENGTH];
+
+        result[0] = b'8';
+        result[1] = (&self.segmento).into();
+        result[2] = (&self.tipo_valor).into();
+
+        let valor = self.valor.unwrap_or(Valor::ZERO).centavos();
+        if valor > 99_999_999_999 {
+            return Err(BoletoError::ValorOverflow);
+        }
+        escrever_digitos(&mut result[4..15], valor);
+
+        let inicio_campo_livre = match (&self.segmento, &self.convenio) {
+            (Segmento::Carnes, Convenio::Carne(cnpj)) if cnpj.iter().all(u8::is_ascii_digit) => {
+                result[15..23].copy_from_slice(cnpj);
+                23
+            },
+            (Segmento::Carnes, _) | (_, Convenio::Carne(_)) => return Err(BoletoError::InvalidConvenio),
+            (_, Convenio::Outros(numero)) if *numero <= 9999 => {
+                escrever_digitos(&mut result[15..19], (*numero).into());
+                19
+            },
+            (_, Convenio::Outros(_)) => return Err(BoletoError::InvalidConvenio),
+        };
+
+        let tamanho_campo_livre = CodBarrasArr::LENGTH - inicio_campo_livre;
+        let campo_livre = match self.campo_livre.digitos() {
+            Some(digitos) if digitos.len() <= tamanho_campo_livre => digitos,
+            _ => return Err(BoletoError::InvalidCampoLivre),
+        };
+        result[CodBarrasArr::LENGTH - campo_livre.len()..].copy_from_slice(campo_livre);
+
+        let mut cod_barras = CodBarrasArr::new(&result)?;
+        cod_barras.update_dv();
+
+        Ok(cod_barras)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn build(self) -> Result<Arrecadacao, BoletoError> {
+        Arrecadacao::new(self.build_cod_barras()?.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cobranca::LinhaDigitavel;
+    use crate::fator_vencimento::DataCivil;
+
+    #[test]
+    fn build_cod_barras_without_std() {
+        let cod_barras = CobrancaBuilder::new()
+            .cod_moeda(CodigoMoeda::Real)
+            .cod_banco(CodBanco(341))
+            .valor(Valor::from_centavos(12345))
+            .data_vencimento(Data::new(2026, 12, 21).unwrap())
+            .campo_livre(b"1101234567880057123457000")
+            .build_cod_barras()
+            .unwrap();
+
+        assert_eq!(cod_barras.as_str(), "34196166700000123451101234567880057123457000");
+        assert_eq!(cod_barras.data_vencimento(), Data::new(2026, 12, 21));
+        assert_eq!(cod_barras.valor(), Some(Valor::from_centavos(12345)));
+        assert!(cod_barras.validate().is_ok());
+        assert!(LinhaDigitavel::from(&cod_barras).validate().is_ok());
+
+        let cod_barras = ArrecadacaoBuilder::new()
+            .segmento(Segmento::Saneamento)
+            .tipo_valor(TipoValor::ValorReaisMod10)
+            .convenio(Convenio::Outros(1346))
+            .build_cod_barras()
+            .unwrap();
+
+        assert_eq!(cod_barras.as_str(), "82690000000000013460000000000000000000000000");
+        assert!(cod_barras.validate().is_ok());
+    }
+
+    #[test]
+    fn build_cod_barras_invalid_campo_livre() {
+        for campo_livre in [&b"123"[..], b"11012345678800571234570001", b"110123456788005712345700a"] {
+            assert!(matches!(
+                CobrancaBuilder::new()
+                    .cod_moeda(CodigoMoeda::Real)
+                    .cod_banco(CodBanco(341))
+                    .campo_livre(campo_livre)
+                    .build_cod_barras(),
+                Err(BoletoError::InvalidCampoLivre),
+            ));
+        }
+
+        assert!(matches!(
+            ArrecadacaoBuilder::new()
+                .segmento(Segmento::Saneamento)
+                .tipo_valor(TipoValor::ValorReaisMod10)
+                .convenio(Convenio::Outros(1346))
+                .campo_livre(b"123456789012345678901234567890")
+                .build_cod_barras(),
+            Err(BoletoError::InvalidCampoLivre),
+        ));
     }
 }
@@ -0,0 +1,306 @@
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use crate::cobranca::Cobranca;
+use crate::fator_vencimento::{parse_data, Data, DataCivil, ANO_MAX, ANO_MIN};
+use crate::BoletoError;
+
+/// Data do domingo de Páscoa (algoritmo de Meeus/Jones/Butcher). Anos fora do
+/// intervalo suportado por `DataCivil` retornam `None`.
+pub fn pascoa(ano: i32) -> Option<Data> {
+    if !(ANO_MIN..=ANO_MAX).contains(&ano) {
+        return None;
+    }
+
+    let a = ano % 19;
+    let b = ano / 100;
+    let c = ano % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let mes = (h + l - 7 * m + 114) / 31;
+    let dia = (h + l - 7 * m + 114) % 31 + 1;
+
+    Data::new(ano, mes as u32, dia as u32)
+}
+
+/// Feriados nacionais sem expediente bancário, em ordem cronológica.
+///
+/// Inclui Carnaval (segunda e terça), Sexta-feira Santa e Corpus Christi, que
+/// não são feriados nacionais por lei mas não têm expediente bancário.
+pub fn feriados_nacionais(ano: i32) -> Vec<Data> {
+    let mut fixos = vec![(1, 1), (4, 21), (5, 1), (9, 7), (10, 12), (11, 2), (11, 15), (12, 25)];
+
+    // Dia Nacional de Zumbi e da Consciência Negra (Lei 14.759/2023)
+    if ano >= 2024 {
+        fixos.push((11, 20));
+    }
+
+    let moveis = pascoa(ano)
+        .into_iter()
+        .flat_map(|pascoa| [-48, -47, -2, 60].map(|dias| pascoa.somar_dias(dias)))
+        .flatten();
+
+    let mut feriados: Vec<Data> = fixos
+        .into_iter()
+        .filter_map(|(mes, dia)| Data::new(ano, mes, dia))
+        .chain(moveis)
+        .collect();
+
+    feriados.sort();
+    feriados
+}
+
+/// Calendário de dias úteis bancários: feriados nacionais mais os feriados
+/// adicionais (municipais ou estaduais) informados.
+#[derive(Debug, Clone, Default)]
+pub struct Calendario {
+    feriados: BTreeSet<Data>,
+    /// Feriados que se repetem todo ano, como `(mês, dia)`
+    feriados_anuais: BTreeSet<(u32, u32)>,
+}
+
+impl Calendario {
+    /// Calendário apenas com os feriados nacionais
+    pub fn nacional() -> Self {
+        Self::default()
+    }
+
+    /// Lê feriados adicionais de um CSV com as colunas `data,nome`.
+    ///
+    /// A data pode ser `AAAA-MM-DD` para um feriado único ou `MM-DD` para um
+    /// feriado que se repete todo ano.
+    pub fn from_csv<R: io::Read>(reader: R) -> Result<Self, BoletoError> {
+        let mut calendario = Self::default();
+        let mut reader = csv::Reader::from_reader(reader);
+
+        for (i, record) in reader.records().enumerate() {
+            let record = record.map_err(|e| BoletoError::InvalidFeriados {
+                linha: e.position().map(|posicao| posicao.line()),
+            })?;
+            let data = record.get(0).unwrap_or_default().trim();
+            let invalid = || BoletoError::InvalidFeriados { linha: Some(i as u64 + 2) };
+
+            if let Ok(date) = parse_data(data) {
+                calendario.adicionar_feriado(date);
+                continue;
+            }
+
+            let (mes, dia) = data.split_once('-').ok_or_else(invalid)?;
+            let (mes, dia) = (mes.parse().map_err(|_| invalid())?, dia.parse().map_err(|_| invalid())?);
+
+            // 2000 é bissexto, então 29/02 também é aceito
+            if Data::new(2000, mes, dia).is_none() {
+                return Err(invalid());
+            }
+
+            calendario.adicionar_feriado_anual(mes, dia);
+        }
+
+        Ok(calendario)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, BoletoError> {
+        let file = File::open(path).map_err(|_| BoletoError::InvalidFeriados { linha: None })?;
+
+        Self::from_csv(file)
+    }
+
+    pub fn adicionar_feriado(&mut self, data: Data) {
+        self.feriados.insert(data);
+    }
+
+    pub fn adicionar_feriado_anual(&mut self, mes: u32, dia: u32) {
+        self.feriados_anuais.insert((mes, dia));
+    }
+
+    pub fn is_feriado(&self, data: Data) -> bool {
+        self.feriados.contains(&data)
+            || self.feriados_anuais.contains(&(data.mes(), data.dia()))
+            || feriados_nacionais(data.ano()).contains(&data)
+    }
+
+    pub fn is_dia_util(&self, data: Data) -> bool {
+        !data.is_fim_de_semana() && !self.is_feriado(data)
+    }
+
+    /// A própria data, se for dia útil, ou o primeiro dia útil seguinte
+    pub fn proximo_dia_util(&self, data: Data) -> Data {
+        (0..)
+            .map_while(|dias| data.somar_dias(dias))
+            .find(|d| self.is_dia_util(*d))
+            .expect("sempre existe um dia útil após qualquer data representável")
+    }
+}
+
+/// Boletos que vencem em dia não útil podem ser pagos sem encargos no primeiro
+/// dia útil seguinte. As datas são `fator_vencimento::Data`, ou seja,
+/// `chrono::NaiveDate` com a feature `chrono`.
+impl Cobranca {
+    /// Último dia de pagamento sem multa ou juros considerando apenas os
+    /// feriados nacionais. Boletos sem vencimento retornam `None`.
+    pub fn data_limite_pagamento_sem_encargos(&self) -> Option<Data> {
+        self.data_limite_pagamento_sem_encargos_com(&Calendario::nacional())
+    }
+
+    pub fn data_limite_pagamento_sem_encargos_com(&self, calendario: &Calendario) -> Option<Data> {
+        self.data_vencimento.map(|data| calendario.proximo_dia_util(data))
+    }
+
+    /// Indica se o pagamento na data `em` já é posterior à data limite.
+    /// Boletos sem vencimento nunca estão vencidos.
+    pub fn is_vencido(&self, em: Data) -> bool {
+        self.is_vencido_com(em, &Calendario::nacional())
+    }
+
+    pub fn is_vencido_com(&self, em: Data, calendario: &Calendario) -> bool {
+        self.data_limite_pagamento_sem_encargos_com(calendario)
+            .is_some_and(|limite| em > limite)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::CobrancaBuilder;
+    use crate::cobranca::{CodBanco, CodigoMoeda};
+
+    fn date(y: i32, m: u32, d: u32) -> Data {
+        Data::new(y, m, d).unwrap()
+    }
+
+    fn cobranca(data_vencimento: Option<Data>) -> Cobranca {
+        let builder = CobrancaBuilder::new()
+            .cod_banco(CodBanco(1))
+            .cod_moeda(CodigoMoeda::Real);
+
+        match data_vencimento {
+            Some(data) => builder.data_vencimento(data).build().unwrap(),
+            None => builder.build().unwrap(),
+        }
+    }
+
+    #[test]
+    fn calculate_pascoa_correctly() {
+        assert_eq!(pascoa(2000), Some(date(2000, 4, 23)));
+        assert_eq!(pascoa(2019), Some(date(2019, 4, 21)));
+        assert_eq!(pascoa(2024), Some(date(2024, 3, 31)));
+        assert_eq!(pascoa(2025), Some(date(2025, 4, 20)));
+        assert_eq!(pascoa(2026), Some(date(2026, 4, 5)));
+
+        assert!(pascoa(ANO_MIN).is_some());
+        assert!(pascoa(ANO_MAX).is_some());
+        for ano in [i32::MIN, -1, 0, 10_000, i32::MAX] {
+            assert_eq!(pascoa(ano), None);
+            assert!(feriados_nacionais(ano).is_empty());
+        }
+    }
+
+    #[test]
+    fn list_feriados_nacionais_correctly() {
+        assert_eq!(
+            feriados_nacionais(2025),
+            vec![
+                date(2025, 1, 1),
+                date(2025, 3, 3),
+                date(2025, 3, 4),
+                date(2025, 4, 18),
+                date(2025, 4, 21),
+                date(2025, 5, 1),
+                date(2025, 6, 19),
+                date(2025, 9, 7),
+                date(2025, 10, 12),
+                date(2025, 11, 2),
+                date(2025, 11, 15),
+                date(2025, 11, 20),
+                date(2025, 12, 25),
+            ],
+        );
+        assert!(!feriados_nacionais(2023).contains(&date(2023, 11, 20)));
+    }
+
+    #[test]
+    fn find_proximo_dia_util_correctly() {
+        let calendario = Calendario::nacional();
+
+        // Dia útil
+        assert_eq!(calendario.proximo_dia_util(date(2025, 2, 20)), date(2025, 2, 20));
+        // Sábado
+        assert_eq!(calendario.proximo_dia_util(date(2025, 2, 22)), date(2025, 2, 24));
+        // Sábado antes do Carnaval
+        assert_eq!(calendario.proximo_dia_util(date(2025, 3, 1)), date(2025, 3, 5));
+        // Sexta-feira Santa
+        assert_eq!(calendario.proximo_dia_util(date(2025, 4, 18)), date(2025, 4, 22));
+    }
+
+    #[test]
+    fn load_feriados_municipais_correctly() {
+        let csv = "data,nome\n01-25,Aniversário de São Paulo\n2025-07-09,Revolução Constitucionalista\n";
+        let calendario = Calendario::from_csv(csv.as_bytes()).unwrap();
+
+        assert!(calendario.is_feriado(date(2025, 1, 25)));
+        assert!(calendario.is_feriado(date(2027, 1, 25)));
+        assert!(calendario.is_feriado(date(2025, 7, 9)));
+        assert!(!calendario.is_feriado(date(2026, 7, 9)));
+        assert!(!Calendario::nacional().is_feriado(date(2027, 1, 25)));
+        // 25/01/2027 é uma segunda-feira
+        assert_eq!(calendario.proximo_dia_util(date(2027, 1, 23)), date(2027, 1, 26));
+
+        for csv in ["data,nome\n2025-13-01,X\n", "data,nome\n02-30,X\n", "data,nome\nontem,X\n"] {
+            assert!(matches!(
+                Calendario::from_csv(csv.as_bytes()),
+                Err(BoletoError::InvalidFeriados { linha: Some(2) }),
+            ));
+        }
+
+        let erro = Calendario::from_file("/caminho/que/nao/existe.csv").unwrap_err();
+        assert_eq!(erro, BoletoError::InvalidFeriados { linha: None });
+        assert_eq!(erro.to_string(), "arquivo de feriados inválido");
+        assert_eq!(
+            BoletoError::InvalidFeriados { linha: Some(3) }.to_string(),
+            "arquivo de feriados inválido na linha 3",
+        );
+    }
+
+    #[test]
+    fn check_cobranca_vencida_correctly() {
+        // Vence no sábado de Carnaval e pode ser paga até quarta-feira de cinzas
+        let boleto = cobranca(Some(date(2025, 3, 1)));
+
+        assert_eq!(boleto.data_limite_pagamento_sem_encargos(), Some(date(2025, 3, 5)));
+        assert!(!boleto.is_vencido(date(2025, 3, 5)));
+        assert!(boleto.is_vencido(date(2025, 3, 6)));
+
+        let mut calendario = Calendario::nacional();
+        calendario.adicionar_feriado(date(2025, 3, 5));
+
+        assert_eq!(boleto.data_limite_pagamento_sem_encargos_com(&calendario), Some(date(2025, 3, 6)));
+        assert!(!boleto.is_vencido_com(date(2025, 3, 6), &calendario));
+
+        let boleto = cobranca(None);
+
+        assert_eq!(boleto.data_limite_pagamento_sem_encargos(), None);
+        assert!(!boleto.is_vencido(date(2049, 1, 1)));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn check_cobranca_vencida_with_naive_date() {
+        use chrono::NaiveDate;
+
+        let boleto = cobranca(NaiveDate::from_ymd_opt(2025, 3, 1));
+        let limite: Option<NaiveDate> = boleto.data_limite_pagamento_sem_encargos();
+
+        assert_eq!(limite, NaiveDate::from_ymd_opt(2025, 3, 5));
+        assert!(!boleto.is_vencido(NaiveDate::from_ymd_opt(2025, 3, 5).unwrap()));
+        assert!(boleto.is_vencido(NaiveDate::from_ymd_opt(2025, 3, 6).unwrap()));
+    }
+}
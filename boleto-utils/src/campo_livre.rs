@@ -0,0 +1,288 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::cobranca::{CodBanco, CodBarras};
+use crate::BoletoError;
+
+mod banco_do_brasil;
+mod bradesco;
+mod caixa;
+mod itau;
+mod santander;
+
+pub use banco_do_brasil::BancoDoBrasil;
+pub use bradesco::Bradesco;
+pub use caixa::{Caixa, CodigoBeneficiario};
+pub use itau::Itau;
+pub use santander::Santander;
+
+/// Sequência de dígitos de tamanho fixo extraída do campo livre
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Digitos<const N: usize>([u8; N]);
+
+impl<const N: usize> Digitos<N> {
+    /// Copia os `N` primeiros bytes de `input`, que devem ser dígitos ASCII
+    pub(crate) fn new(input: &[u8]) -> Self {
+        let mut digitos = [0u8; N];
+        digitos.copy_from_slice(&input[..N]);
+
+        debug_assert!(digitos.iter().all(|c| c.is_ascii_digit()));
+
+        Self(digitos)
+    }
+
+    pub fn as_str(&self) -> &str {
+        unsafe { std::str::from_utf8_unchecked(&self.0) }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> FromStr for Digitos<N> {
+    type Err = BoletoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != N {
+            return Err(BoletoError::InvalidLength);
+        }
+
+        if !s.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(BoletoError::NumbersOnly);
+        }
+
+        Ok(Self::new(s.as_bytes()))
+    }
+}
+
+impl<const N: usize> fmt::Debug for Digitos<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Digitos")
+            .field(&self.as_str())
+            .finish()
+    }
+}
+
+impl<const N: usize> fmt::Display for Digitos<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> Serialize for Digitos<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> Deserialize<'de> for Digitos<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de> {
+        let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Decodificadores de campo livre indexados pelo código do banco.
+///
+/// `Decoders::default()` contém os decodificadores embutidos na crate. Use
+/// com `Boleto::new_with` ou `Cobranca::new_with` para decodificar com um
+/// conjunto próprio sem alterar o registro global (ver `registrar`).
+pub struct Decoders(HashMap<u16, DecoderOpcional>);
+
+/// Decodificador que pode deixar o campo livre sem decodificar, usado pelos
+/// embutidos quando o layout não pode ser determinado
+type DecoderOpcional = Box<dyn Fn(&CodBarras) -> Result<Option<CampoLivre>, BoletoError> + Send + Sync>;
+
+impl Decoders {
+    /// Conjunto sem nenhum decodificador
+    pub fn vazio() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Registra o decodificador de campo livre de um banco, substituindo o
+    /// decodificador anterior
+    pub fn registrar<D>(&mut self, cod_banco: CodBanco, decoder: D)
+    where
+        D: CampoLivreDecoder + 'static,
+    {
+        self.0.insert(cod_banco.0, Box::new(move |cod_barras: &CodBarras| decoder.decode(cod_barras).map(Some)));
+    }
+
+    /// Remove o decodificador de campo livre de um banco, retornando se havia um registrado
+    pub fn remover(&mut self, cod_banco: CodBanco) -> bool {
+        self.0.remove(&cod_banco.0).is_some()
+    }
+
+    /// Decodifica o campo livre do código de barras.
+    ///
+    /// Retorna `Ok(None)` quando não há decodificador para o banco ou quando o
+    /// decodificador embutido não consegue determinar o layout do campo livre.
+    pub fn decode(&self, cod_barras: &CodBarras) -> Result<Option<CampoLivre>, BoletoError> {
+        match self.0.get(&cod_barras.cod_banco().0) {
+            Some(decoder) => decoder(cod_barras),
+            None => Ok(None),
+        }
+    }
+}
+
+impl Default for Decoders {
+    fn default() -> Self {
+        let mut decoders = Self::vazio();
+
+        decoders.registrar(CodBanco(1), |cod_barras: &CodBarras| {
+            Ok(CampoLivre::BancoDoBrasil(BancoDoBrasil::new(cod_barras.campo_livre())))
+        });
+        decoders.registrar(CodBanco(33), |cod_barras: &CodBarras| {
+            Ok(CampoLivre::Santander(Santander::new(cod_barras.campo_livre())?))
+        });
+        decoders.0.insert(104, Box::new(|cod_barras: &CodBarras| {
+            Ok(Caixa::new(cod_barras.campo_livre())?.map(CampoLivre::Caixa))
+        }));
+        decoders.registrar(CodBanco(237), |cod_barras: &CodBarras| {
+            Ok(CampoLivre::Bradesco(Bradesco::new(cod_barras.campo_livre())))
+        });
+        decoders.registrar(CodBanco(341), |cod_barras: &CodBarras| {
+            Ok(CampoLivre::Itau(Itau::new(cod_barras.campo_livre())?))
+        });
+
+        decoders
+    }
+}
+
+impl fmt::Debug for Decoders {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bancos: Vec<_> = self.0.keys().collect();
+        bancos.sort();
+
+        f.debug_tuple("Decoders").field(&bancos).finish()
+    }
+}
+
+lazy_static! {
+    /// Registro global, usado por `Boleto::new` e `Cobranca::new`
+    static ref DECODERS: RwLock<Decoders> = RwLock::new(Decoders::default());
+}
+
+/// Decodificador do campo livre de um banco.
+///
+/// Um erro não invalida o boleto: `Cobranca::new` o informa em
+/// `campo_livre_error` e deixa `campo_livre` vazio.
+///
+/// Implementado também para closures `Fn(&CodBarras) -> Result<CampoLivre, BoletoError>`.
+pub trait CampoLivreDecoder: Send + Sync {
+    fn decode(&self, cod_barras: &CodBarras) -> Result<CampoLivre, BoletoError>;
+}
+
+impl<F> CampoLivreDecoder for F
+where
+    F: Fn(&CodBarras) -> Result<CampoLivre, BoletoError> + Send + Sync,
+{
+    fn decode(&self, cod_barras: &CodBarras) -> Result<CampoLivre, BoletoError> {
+        self(cod_barras)
+    }
+}
+
+/// Registra o decodificador de campo livre de um banco no registro global,
+/// substituindo o decodificador anterior (inclusive os embutidos na crate).
+///
+/// O registro global vale para todo o processo: a alteração afeta todas as
+/// chamadas a `Boleto::new` e `Cobranca::new`, inclusive as feitas por outras
+/// bibliotecas e por testes executados em paralelo. Para não afetar o restante
+/// do programa, prefira `Decoders` com `Boleto::new_with`.
+pub fn registrar<D>(cod_banco: CodBanco, decoder: D)
+where
+    D: CampoLivreDecoder + 'static,
+{
+    DECODERS
+        .write()
+        .expect("registro de decodificadores de campo livre envenenado")
+        .registrar(cod_banco, decoder);
+}
+
+/// Remove o decodificador de campo livre de um banco do registro global,
+/// retornando se havia um registrado (ver `registrar`)
+pub fn remover(cod_banco: CodBanco) -> bool {
+    DECODERS
+        .write()
+        .expect("registro de decodificadores de campo livre envenenado")
+        .remover(cod_banco)
+}
+
+/// Codificador do campo livre de um banco, usado pelo `CobrancaBuilder`.
+///
+/// Os dígitos verificadores internos do campo livre são sempre recalculados,
+/// os campos de DV da estrutura codificada são ignorados. Falha quando algum
+/// campo não pode ser representado no layout do banco.
+pub trait CampoLivreEncoder {
+    fn encode(&self) -> Result<[u8; CampoLivre::LENGTH], BoletoError>;
+}
+
+/// Campo livre decodificado por um decodificador registrado fora da crate
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Generico {
+    pub nosso_numero: String,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub campos: BTreeMap<String, String>,
+}
+
+/// Campo livre (posições 20 a 44 do código de barras) decodificado de acordo
+/// com o layout do banco emissor
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "banco"))]
+pub enum CampoLivre {
+    #[cfg_attr(feature = "serde", serde(rename = "banco_do_brasil"))]
+    BancoDoBrasil(BancoDoBrasil),
+    #[cfg_attr(feature = "serde", serde(rename = "caixa"))]
+    Caixa(Caixa),
+    #[cfg_attr(feature = "serde", serde(rename = "bradesco"))]
+    Bradesco(Bradesco),
+    #[cfg_attr(feature = "serde", serde(rename = "itau"))]
+    Itau(Itau),
+    #[cfg_attr(feature = "serde", serde(rename = "santander"))]
+    Santander(Santander),
+    #[cfg_attr(feature = "serde", serde(rename = "outro"))]
+    Outro(Generico),
+}
+
+impl CampoLivre {
+    pub const LENGTH: usize = 25;
+
+    /// Decodifica o campo livre do código de barras com o registro global.
+    ///
+    /// Retorna `Ok(None)` quando não há decodificador registrado para o banco
+    /// (ver `Decoders::decode`).
+    pub fn new(cod_barras: &CodBarras) -> Result<Option<Self>, BoletoError> {
+        DECODERS
+            .read()
+            .expect("registro de decodificadores de campo livre envenenado")
+            .decode(cod_barras)
+    }
+
+    /// Nosso número do título
+    pub fn nosso_numero(&self) -> &str {
+        match self {
+            Self::BancoDoBrasil(campo_livre) => campo_livre.nosso_numero(),
+            Self::Caixa(campo_livre) => campo_livre.nosso_numero.as_str(),
+            Self::Bradesco(campo_livre) => campo_livre.nosso_numero.as_str(),
+            Self::Itau(campo_livre) => campo_livre.nosso_numero(),
+            Self::Santander(campo_livre) => campo_livre.nosso_numero.as_str(),
+            Self::Outro(campo_livre) => campo_livre.nosso_numero.as_str(),
+        }
+    }
+}
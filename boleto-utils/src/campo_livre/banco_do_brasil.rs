@@ -0,0 +1,158 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{CampoLivre, CampoLivreEncoder, Digitos};
+use crate::BoletoError;
+
+/// Campo livre do Banco do Brasil (001)
+///
+/// O layout varia de acordo com o tamanho do número do convênio:
+///
+/// ```text
+/// Convênio de 7 posições:        000000 CCCCCCCNNNNNNNNNN KK
+/// Convênio de 6 posições (NN17): CCCCCC NNNNNNNNNNNNNNNNN 21
+/// Convênio de 4 ou 6 posições:   NNNNNNNNNNN AAAA CCCCCCCC KK
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "variante"))]
+pub enum BancoDoBrasil {
+    /// Convênio de 7 posições. O nosso número (17 posições) é composto pelo
+    /// convênio seguido de um complemento de 10 posições.
+    #[cfg_attr(feature = "serde", serde(rename = "convenio_7"))]
+    Convenio7 {
+        convenio: Digitos<7>,
+        nosso_numero: Digitos<17>,
+        carteira: Digitos<2>,
+    },
+    /// Convênio de 6 posições com nosso número livre de 17 posições
+    /// (identificado pelo código de serviço "21" no final do campo livre)
+    #[cfg_attr(feature = "serde", serde(rename = "convenio_6"))]
+    Convenio6 {
+        convenio: Digitos<6>,
+        nosso_numero: Digitos<17>,
+    },
+    /// Convênio de 4 ou 6 posições com nosso número de 11 posições.
+    ///
+    /// O nosso número começa com o convênio (4 posições seguidas de 7 de
+    /// sequencial, ou 6 posições seguidas de 5), mas não é possível distinguir
+    /// os dois casos apenas pelo código de barras.
+    #[cfg_attr(feature = "serde", serde(rename = "convenio_4_ou_6"))]
+    Convenio4Ou6 {
+        nosso_numero: Digitos<11>,
+        agencia: Digitos<4>,
+        conta: Digitos<8>,
+        carteira: Digitos<2>,
+    },
+}
+
+impl BancoDoBrasil {
+    pub(crate) fn new(campo_livre: &[u8]) -> Self {
+        match campo_livre {
+            [b'0', b'0', b'0', b'0', b'0', b'0', ..] => Self::Convenio7 {
+                convenio: Digitos::new(&campo_livre[6..13]),
+                nosso_numero: Digitos::new(&campo_livre[6..23]),
+                carteira: Digitos::new(&campo_livre[23..25]),
+            },
+            [.., b'2', b'1'] => Self::Convenio6 {
+                convenio: Digitos::new(&campo_livre[0..6]),
+                nosso_numero: Digitos::new(&campo_livre[6..23]),
+            },
+            _ => Self::Convenio4Ou6 {
+                nosso_numero: Digitos::new(&campo_livre[0..11]),
+                agencia: Digitos::new(&campo_livre[11..15]),
+                conta: Digitos::new(&campo_livre[15..23]),
+                carteira: Digitos::new(&campo_livre[23..25]),
+            },
+        }
+    }
+
+    pub fn nosso_numero(&self) -> &str {
+        match self {
+            Self::Convenio7 { nosso_numero, .. } => nosso_numero.as_str(),
+            Self::Convenio6 { nosso_numero, .. } => nosso_numero.as_str(),
+            Self::Convenio4Ou6 { nosso_numero, .. } => nosso_numero.as_str(),
+        }
+    }
+}
+
+/// No convênio de 7 posições, o convênio é lido do próprio nosso número
+impl CampoLivreEncoder for BancoDoBrasil {
+    fn encode(&self) -> Result<[u8; CampoLivre::LENGTH], BoletoError> {
+        let mut campo_livre = [b'0'; CampoLivre::LENGTH];
+
+        match self {
+            Self::Convenio7 { nosso_numero, carteira, .. } => {
+                campo_livre[6..23].copy_from_slice(nosso_numero.as_bytes());
+                campo_livre[23..25].copy_from_slice(carteira.as_bytes());
+            },
+            Self::Convenio6 { convenio, nosso_numero } => {
+                campo_livre[0..6].copy_from_slice(convenio.as_bytes());
+                campo_livre[6..23].copy_from_slice(nosso_numero.as_bytes());
+                campo_livre[23..25].copy_from_slice(b"21");
+            },
+            Self::Convenio4Ou6 { nosso_numero, agencia, conta, carteira } => {
+                campo_livre[0..11].copy_from_slice(nosso_numero.as_bytes());
+                campo_livre[11..15].copy_from_slice(agencia.as_bytes());
+                campo_livre[15..23].copy_from_slice(conta.as_bytes());
+                campo_livre[23..25].copy_from_slice(carteira.as_bytes());
+            },
+        }
+
+        Ok(campo_livre)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_convenio_7_correctly() {
+        let BancoDoBrasil::Convenio7 { convenio, nosso_numero, carteira } = BancoDoBrasil::new(b"0000002656973019362470618") else {
+            panic!("Deveria ser convênio de 7 posições");
+        };
+
+        assert_eq!(convenio.as_str(), "2656973");
+        assert_eq!(nosso_numero.as_str(), "26569730193624706");
+        assert_eq!(carteira.as_str(), "18");
+    }
+
+    #[test]
+    fn decode_convenio_6_correctly() {
+        let BancoDoBrasil::Convenio6 { convenio, nosso_numero } = BancoDoBrasil::new(b"1234561234567890123456721") else {
+            panic!("Deveria ser convênio de 6 posições");
+        };
+
+        assert_eq!(convenio.as_str(), "123456");
+        assert_eq!(nosso_numero.as_str(), "12345678901234567");
+    }
+
+    #[test]
+    fn decode_convenio_4_ou_6_correctly() {
+        let campo_livre = BancoDoBrasil::new(b"1234000012301230004567817");
+
+        assert_eq!(campo_livre.nosso_numero(), "12340000123");
+
+        let BancoDoBrasil::Convenio4Ou6 { agencia, conta, carteira, .. } = campo_livre else {
+            panic!("Deveria ser convênio de 4 ou 6 posições");
+        };
+
+        assert_eq!(agencia.as_str(), "0123");
+        assert_eq!(conta.as_str(), "00045678");
+        assert_eq!(carteira.as_str(), "17");
+    }
+
+    #[test]
+    fn encode_round_trip() {
+        let campos_livres = [
+            b"0000002656973019362470618",
+            b"1234561234567890123456721",
+            b"1234000012301230004567817",
+        ];
+
+        for campo_livre in campos_livres {
+            assert_eq!(&BancoDoBrasil::new(campo_livre).encode().unwrap(), campo_livre);
+        }
+    }
+}
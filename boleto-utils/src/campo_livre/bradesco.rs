@@ -0,0 +1,108 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{CampoLivre, CampoLivreEncoder, Digitos};
+use crate::BoletoError;
+
+/// Campo livre do Bradesco (237)
+///
+/// ```text
+/// 00000000001111111111222222
+/// 01234567890123456789012345
+/// AAAAKKNNNNNNNNNNNCCCCCCC0
+/// ```
+///
+/// - A: agência beneficiária (sem DV)
+/// - K: carteira
+/// - N: nosso número (sem DV)
+/// - C: conta do beneficiário (sem DV)
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Bradesco {
+    pub agencia: Digitos<4>,
+    pub carteira: Digitos<2>,
+    pub nosso_numero: Digitos<11>,
+    /// Dígito de autoconferência do nosso número, que pode ser 'P'
+    pub dv_nosso_numero: char,
+    pub conta: Digitos<7>,
+}
+
+impl Bradesco {
+    pub(crate) fn new(campo_livre: &[u8]) -> Self {
+        Self {
+            agencia: Digitos::new(&campo_livre[0..4]),
+            carteira: Digitos::new(&campo_livre[4..6]),
+            nosso_numero: Digitos::new(&campo_livre[6..17]),
+            dv_nosso_numero: dv_nosso_numero(&campo_livre[4..17]),
+            conta: Digitos::new(&campo_livre[17..24]),
+        }
+    }
+
+    /// Nosso número no formato impresso no boleto: `carteira/nosso número-DV`
+    pub fn nosso_numero_formatado(&self) -> String {
+        format!("{}/{}-{}", self.carteira, self.nosso_numero, self.dv_nosso_numero)
+    }
+}
+
+impl CampoLivreEncoder for Bradesco {
+    fn encode(&self) -> Result<[u8; CampoLivre::LENGTH], BoletoError> {
+        let mut campo_livre = [b'0'; CampoLivre::LENGTH];
+
+        campo_livre[0..4].copy_from_slice(self.agencia.as_bytes());
+        campo_livre[4..6].copy_from_slice(self.carteira.as_bytes());
+        campo_livre[6..17].copy_from_slice(self.nosso_numero.as_bytes());
+        campo_livre[17..24].copy_from_slice(self.conta.as_bytes());
+
+        Ok(campo_livre)
+    }
+}
+
+/// Módulo 11 com base 7 sobre a carteira seguida do nosso número.
+///
+/// Resto 0 resulta em '0' e resto 1 resulta em 'P'.
+fn dv_nosso_numero(carteira_nosso_numero: &[u8]) -> char {
+    let soma: usize = carteira_nosso_numero.iter()
+        .rev()
+        .zip((2..=7).cycle())
+        .map(|(n, i)| (n - b'0') as usize * i)
+        .sum();
+
+    match soma % 11 {
+        0 => '0',
+        1 => 'P',
+        resto => char::from(b'0' + (11 - resto) as u8),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_campo_livre_correctly() {
+        let campo_livre = Bradesco::new(b"2028269705944177105205220");
+
+        assert_eq!(campo_livre.agencia.as_str(), "2028");
+        assert_eq!(campo_livre.carteira.as_str(), "26");
+        assert_eq!(campo_livre.nosso_numero.as_str(), "97059441771");
+        assert_eq!(campo_livre.conta.as_str(), "0520522");
+        assert_eq!(&campo_livre.encode().unwrap(), b"2028269705944177105205220");
+    }
+
+    #[test]
+    fn calculate_dv_nosso_numero_correctly() {
+        let cases = [
+            (b"1900000000002", '8'),
+            (b"1900000000001", 'P'),
+            (b"1900000000006", '0'),
+        ];
+
+        for (carteira_nosso_numero, expected) in cases {
+            assert_eq!(dv_nosso_numero(carteira_nosso_numero), expected);
+        }
+
+        let campo_livre = Bradesco::new(b"1234190000000000200123450");
+
+        assert_eq!(campo_livre.nosso_numero_formatado(), "19/00000000002-8");
+    }
+}
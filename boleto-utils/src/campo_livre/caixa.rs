@@ -0,0 +1,250 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{CampoLivre, CampoLivreEncoder, Digitos};
+use crate::utils::dv_utils;
+use crate::BoletoError;
+
+/// Código do beneficiário da Caixa
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum CodigoBeneficiario {
+    /// Código entre 000001 e 999999, seguido do seu dígito verificador (módulo 11)
+    SeisDigitos { codigo: Digitos<6>, dv: u8 },
+    /// Código a partir de 1100000, sem dígito verificador
+    SeteDigitos { codigo: Digitos<7> },
+}
+
+impl CodigoBeneficiario {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::SeisDigitos { codigo, .. } => codigo.as_str(),
+            Self::SeteDigitos { codigo } => codigo.as_str(),
+        }
+    }
+}
+
+/// Campo livre da Caixa Econômica Federal (104) no padrão SIGCB
+///
+/// ```text
+/// 00000000001111111111222222
+/// 01234567890123456789012345
+/// BBBBBBDSSSTSSSESSSSSSSSSV
+/// ```
+///
+/// - B: código do beneficiário
+/// - D: DV do código do beneficiário
+/// - S: sequências 1, 2 e 3 do nosso número
+/// - T: constante 1, tipo de cobrança (1 - Registrada)
+/// - E: constante 2, identificador da emissão do boleto (4 - Beneficiário)
+/// - V: DV do campo livre
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Caixa {
+    pub codigo_beneficiario: CodigoBeneficiario,
+    /// Nosso número completo (constante 1, constante 2 e as três sequências)
+    pub nosso_numero: Digitos<17>,
+    pub sequencia_1: Digitos<3>,
+    pub constante_1: u8,
+    pub sequencia_2: Digitos<3>,
+    pub constante_2: u8,
+    pub sequencia_3: Digitos<9>,
+    pub dv_campo_livre: u8,
+}
+
+impl Caixa {
+    /// Decodifica o campo livre, usado pelo decodificador embutido.
+    ///
+    /// O campo livre não indica qual dos layouts foi usado: abaixo de 1100000
+    /// o código é lido com 6 posições e DV; a partir de 1100000, com 7
+    /// posições quando o DV do código de 6 posições não confere. Quando
+    /// confere, os dois layouts são possíveis e retorna `Ok(None)`.
+    pub(crate) fn new(campo_livre: &[u8]) -> Result<Option<Self>, BoletoError> {
+        let dv_seis_digitos = mod_11(&campo_livre[..6]) == campo_livre[6];
+        let sete_digitos = &campo_livre[..7] >= b"1100000";
+
+        match (dv_seis_digitos, sete_digitos) {
+            (true, true) => {
+                if mod_11(&campo_livre[..24]) != campo_livre[24] {
+                    return Err(BoletoError::InvalidDigitoVerificadorCampoLivre);
+                }
+
+                Ok(None)
+            },
+            (false, true) => Self::decode(campo_livre, true).map(Some),
+            (_, false) => Self::decode(campo_livre, false).map(Some),
+        }
+    }
+
+    /// Decodifica o campo livre com código do beneficiário de 7 posições (a
+    /// partir de 1100000).
+    ///
+    /// Os códigos de 7 posições ocupam também a posição do DV do código de 6
+    /// posições. Quando o 7º dígito coincide com esse DV, o decodificador
+    /// embutido não decodifica o campo livre; quem emite com código de 7
+    /// posições pode registrar um decodificador que use este método (ver
+    /// `campo_livre::Decoders`).
+    pub fn new_codigo_sete_digitos(campo_livre: &[u8]) -> Result<Self, BoletoError> {
+        if campo_livre.len() != CampoLivre::LENGTH {
+            return Err(BoletoError::InvalidLength);
+        }
+
+        if !campo_livre.iter().all(|c| c.is_ascii_digit()) {
+            return Err(BoletoError::NumbersOnly);
+        }
+
+        Self::decode(campo_livre, true)
+    }
+
+    fn decode(campo_livre: &[u8], sete_digitos: bool) -> Result<Self, BoletoError> {
+        let dv_campo_livre = mod_11(&campo_livre[..24]);
+
+        if dv_campo_livre != campo_livre[24] {
+            return Err(BoletoError::InvalidDigitoVerificadorCampoLivre);
+        }
+
+        let codigo_beneficiario = if sete_digitos {
+            if &campo_livre[..7] < b"1100000" {
+                return Err(BoletoError::InvalidCampoLivre);
+            }
+
+            CodigoBeneficiario::SeteDigitos { codigo: Digitos::new(&campo_livre[..7]) }
+        } else {
+            if mod_11(&campo_livre[..6]) != campo_livre[6] {
+                return Err(BoletoError::InvalidDigitoVerificadorCampoLivre);
+            }
+
+            CodigoBeneficiario::SeisDigitos {
+                codigo: Digitos::new(&campo_livre[..6]),
+                dv: campo_livre[6] - b'0',
+            }
+        };
+
+        let nosso_numero = {
+            let mut nosso_numero = [0u8; 17];
+
+            nosso_numero[0] = campo_livre[10];
+            nosso_numero[1] = campo_livre[14];
+            nosso_numero[2..5].copy_from_slice(&campo_livre[7..10]);
+            nosso_numero[5..8].copy_from_slice(&campo_livre[11..14]);
+            nosso_numero[8..17].copy_from_slice(&campo_livre[15..24]);
+
+            Digitos::new(&nosso_numero)
+        };
+
+        Ok(Self {
+            codigo_beneficiario,
+            nosso_numero,
+            sequencia_1: Digitos::new(&campo_livre[7..10]),
+            constante_1: campo_livre[10] - b'0',
+            sequencia_2: Digitos::new(&campo_livre[11..14]),
+            constante_2: campo_livre[14] - b'0',
+            sequencia_3: Digitos::new(&campo_livre[15..24]),
+            dv_campo_livre: dv_campo_livre - b'0',
+        })
+    }
+}
+
+/// Codifica a partir do código do beneficiário e do nosso número completo
+impl CampoLivreEncoder for Caixa {
+    fn encode(&self) -> Result<[u8; CampoLivre::LENGTH], BoletoError> {
+        let mut campo_livre = [b'0'; CampoLivre::LENGTH];
+        let nosso_numero = self.nosso_numero.as_bytes();
+
+        match &self.codigo_beneficiario {
+            CodigoBeneficiario::SeisDigitos { codigo, .. } => {
+                campo_livre[0..6].copy_from_slice(codigo.as_bytes());
+                campo_livre[6] = mod_11(codigo.as_bytes());
+            },
+            CodigoBeneficiario::SeteDigitos { codigo } => {
+                campo_livre[0..7].copy_from_slice(codigo.as_bytes());
+            },
+        }
+
+        campo_livre[7..10].copy_from_slice(&nosso_numero[2..5]);
+        campo_livre[10] = nosso_numero[0];
+        campo_livre[11..14].copy_from_slice(&nosso_numero[5..8]);
+        campo_livre[14] = nosso_numero[1];
+        campo_livre[15..24].copy_from_slice(&nosso_numero[8..17]);
+        campo_livre[24] = mod_11(&campo_livre[..24]);
+
+        Ok(campo_livre)
+    }
+}
+
+/// Módulo 11 da Caixa, que admite 0 (zero) quando o resultado é maior que 9
+fn mod_11(values: &[u8]) -> u8 {
+    dv_utils::mod_11(values.iter()).unwrap_or(b'0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_campo_livre_correctly() {
+        let campo_livre = Caixa::new(b"0055077222133347777777771").unwrap().unwrap();
+
+        assert!(matches!(
+            campo_livre.codigo_beneficiario,
+            CodigoBeneficiario::SeisDigitos { dv: 7, .. },
+        ));
+        assert_eq!(campo_livre.codigo_beneficiario.as_str(), "005507");
+        assert_eq!(campo_livre.sequencia_1.as_str(), "222");
+        assert_eq!(campo_livre.constante_1, 1);
+        assert_eq!(campo_livre.sequencia_2.as_str(), "333");
+        assert_eq!(campo_livre.constante_2, 4);
+        assert_eq!(campo_livre.sequencia_3.as_str(), "777777777");
+        assert_eq!(campo_livre.dv_campo_livre, 1);
+        assert_eq!(campo_livre.nosso_numero.as_str(), "14222333777777777");
+    }
+
+    #[test]
+    fn decode_codigo_beneficiario_sete_digitos() {
+        let campo_livre = Caixa::new_codigo_sete_digitos(b"1234567000100040000123450").unwrap();
+
+        assert!(matches!(campo_livre.codigo_beneficiario, CodigoBeneficiario::SeteDigitos { .. }));
+        assert_eq!(campo_livre.codigo_beneficiario.as_str(), "1234567");
+
+        // O DV de "123456" seria 0, então o decodificador embutido lê o
+        // código com 7 posições
+        assert_eq!(Caixa::new(b"1234567000100040000123450").unwrap(), Some(campo_livre));
+
+        // O DV de "110000" é 9: os dois layouts são possíveis
+        assert_eq!(Caixa::new(b"1100009000100040000123458"), Ok(None));
+        assert!(matches!(
+            Caixa::new(b"1100009000100040000123457"),
+            Err(BoletoError::InvalidDigitoVerificadorCampoLivre),
+        ));
+
+        assert!(matches!(
+            Caixa::new_codigo_sete_digitos(b"1099999000100040000123455"),
+            Err(BoletoError::InvalidCampoLivre),
+        ));
+        assert!(matches!(Caixa::new_codigo_sete_digitos(b"123"), Err(BoletoError::InvalidLength)));
+    }
+
+    #[test]
+    fn encode_round_trip() {
+        let campo_livre = b"0055077222133347777777771";
+        assert_eq!(&Caixa::new(campo_livre).unwrap().unwrap().encode().unwrap(), campo_livre);
+
+        let campo_livre = b"1234567000100040000123450";
+        assert_eq!(&Caixa::new_codigo_sete_digitos(campo_livre).unwrap().encode().unwrap(), campo_livre);
+    }
+
+    #[test]
+    fn invalid_digito_verificador() {
+        // DV do campo livre
+        assert!(matches!(
+            Caixa::new(b"0055077222133347777777772"),
+            Err(BoletoError::InvalidDigitoVerificadorCampoLivre),
+        ));
+        // DV do código do beneficiário, abaixo de 1100000
+        assert!(matches!(
+            Caixa::new(b"0055078222133347777777779"),
+            Err(BoletoError::InvalidDigitoVerificadorCampoLivre),
+        ));
+    }
+}
@@ -0,0 +1,227 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{CampoLivre, CampoLivreEncoder, Digitos};
+use crate::utils::dv_utils;
+use crate::BoletoError;
+
+/// Carteiras cujo campo livre contém "seu número" e código do cliente no
+/// lugar de agência e conta
+const CARTEIRAS_SEU_NUMERO: [&[u8; 3]; 8] = [
+    b"106", b"107", b"122", b"142", b"143", b"195", b"196", b"198",
+];
+
+/// Carteiras cujo DAC do nosso número é calculado apenas sobre carteira e
+/// nosso número (sem agência e conta)
+const CARTEIRAS_DAC_SEM_AGENCIA_CONTA: [&[u8; 3]; 6] = [
+    b"126", b"131", b"145", b"146", b"150", b"168",
+];
+
+/// Campo livre do Itaú (341)
+///
+/// ```text
+///             00000000001111111111222222
+///             01234567890123456789012345
+/// Padrão:     KKKNNNNNNNNDAAAACCCCCE000
+/// Seu número: KKKNNNNNNNNSSSSSSSLLLLLD0
+/// ```
+///
+/// - K: carteira
+/// - N: nosso número
+/// - D: DAC (módulo 10)
+/// - A: agência
+/// - C: conta
+/// - E: DAC de agência e conta (módulo 10)
+/// - S: seu número
+/// - L: código do cliente
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "variante"))]
+pub enum Itau {
+    #[cfg_attr(feature = "serde", serde(rename = "padrao"))]
+    Padrao {
+        carteira: Digitos<3>,
+        nosso_numero: Digitos<8>,
+        dac_nosso_numero: u8,
+        agencia: Digitos<4>,
+        conta: Digitos<5>,
+        dac_agencia_conta: u8,
+    },
+    /// Carteiras 106, 107, 122, 142, 143, 195, 196 e 198
+    #[cfg_attr(feature = "serde", serde(rename = "seu_numero"))]
+    SeuNumero {
+        carteira: Digitos<3>,
+        nosso_numero: Digitos<8>,
+        seu_numero: Digitos<7>,
+        codigo_cliente: Digitos<5>,
+        /// DAC de carteira, nosso número, seu número e código do cliente
+        dac: u8,
+    },
+}
+
+impl Itau {
+    pub(crate) fn new(campo_livre: &[u8]) -> Result<Self, BoletoError> {
+        let carteira = &campo_livre[0..3];
+
+        if CARTEIRAS_SEU_NUMERO.iter().any(|c| c.as_slice() == carteira) {
+            let dac = dv_utils::mod_10(campo_livre[0..23].iter());
+
+            if dac != campo_livre[23] {
+                return Err(BoletoError::InvalidDigitoVerificadorCampoLivre);
+            }
+
+            return Ok(Self::SeuNumero {
+                carteira: Digitos::new(carteira),
+                nosso_numero: Digitos::new(&campo_livre[3..11]),
+                seu_numero: Digitos::new(&campo_livre[11..18]),
+                codigo_cliente: Digitos::new(&campo_livre[18..23]),
+                dac: dac - b'0',
+            });
+        }
+
+        let dac_nosso_numero = if CARTEIRAS_DAC_SEM_AGENCIA_CONTA.iter().any(|c| c.as_slice() == carteira) {
+            dv_utils::mod_10(campo_livre[0..11].iter())
+        } else {
+            dv_utils::mod_10(campo_livre[12..21].iter().chain(campo_livre[0..11].iter()))
+        };
+        let dac_agencia_conta = dv_utils::mod_10(campo_livre[12..21].iter());
+
+        if dac_nosso_numero != campo_livre[11] || dac_agencia_conta != campo_livre[21] {
+            return Err(BoletoError::InvalidDigitoVerificadorCampoLivre);
+        }
+
+        Ok(Self::Padrao {
+            carteira: Digitos::new(carteira),
+            nosso_numero: Digitos::new(&campo_livre[3..11]),
+            dac_nosso_numero: dac_nosso_numero - b'0',
+            agencia: Digitos::new(&campo_livre[12..16]),
+            conta: Digitos::new(&campo_livre[16..21]),
+            dac_agencia_conta: dac_agencia_conta - b'0',
+        })
+    }
+
+    pub fn carteira(&self) -> &str {
+        match self {
+            Self::Padrao { carteira, .. } => carteira.as_str(),
+            Self::SeuNumero { carteira, .. } => carteira.as_str(),
+        }
+    }
+
+    pub fn nosso_numero(&self) -> &str {
+        match self {
+            Self::Padrao { nosso_numero, .. } => nosso_numero.as_str(),
+            Self::SeuNumero { nosso_numero, .. } => nosso_numero.as_str(),
+        }
+    }
+}
+
+impl CampoLivreEncoder for Itau {
+    fn encode(&self) -> Result<[u8; CampoLivre::LENGTH], BoletoError> {
+        let mut campo_livre = [b'0'; CampoLivre::LENGTH];
+
+        match self {
+            Self::Padrao { carteira, nosso_numero, agencia, conta, .. } => {
+                campo_livre[0..3].copy_from_slice(carteira.as_bytes());
+                campo_livre[3..11].copy_from_slice(nosso_numero.as_bytes());
+                campo_livre[12..16].copy_from_slice(agencia.as_bytes());
+                campo_livre[16..21].copy_from_slice(conta.as_bytes());
+
+                campo_livre[11] = if CARTEIRAS_DAC_SEM_AGENCIA_CONTA.iter().any(|c| c.as_slice() == carteira.as_bytes()) {
+                    dv_utils::mod_10(campo_livre[0..11].iter())
+                } else {
+                    dv_utils::mod_10(campo_livre[12..21].iter().chain(campo_livre[0..11].iter()))
+                };
+                campo_livre[21] = dv_utils::mod_10(campo_livre[12..21].iter());
+            },
+            Self::SeuNumero { carteira, nosso_numero, seu_numero, codigo_cliente, .. } => {
+                campo_livre[0..3].copy_from_slice(carteira.as_bytes());
+                campo_livre[3..11].copy_from_slice(nosso_numero.as_bytes());
+                campo_livre[11..18].copy_from_slice(seu_numero.as_bytes());
+                campo_livre[18..23].copy_from_slice(codigo_cliente.as_bytes());
+                campo_livre[23] = dv_utils::mod_10(campo_livre[0..23].iter());
+            },
+        }
+
+        Ok(campo_livre)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_padrao_correctly() {
+        let Itau::Padrao {
+            carteira,
+            nosso_numero,
+            dac_nosso_numero,
+            agencia,
+            conta,
+            dac_agencia_conta,
+        } = Itau::new(b"1101234567880057123457000").unwrap() else {
+            panic!("Deveria ser o layout padrão");
+        };
+
+        assert_eq!(carteira.as_str(), "110");
+        assert_eq!(nosso_numero.as_str(), "12345678");
+        assert_eq!(dac_nosso_numero, 8);
+        assert_eq!(agencia.as_str(), "0057");
+        assert_eq!(conta.as_str(), "12345");
+        assert_eq!(dac_agencia_conta, 7);
+
+        // DAC do nosso número sem agência e conta
+        let campo_livre = Itau::new(b"1261234567850057123457000").unwrap();
+
+        assert!(matches!(campo_livre, Itau::Padrao { dac_nosso_numero: 5, .. }));
+    }
+
+    #[test]
+    fn decode_seu_numero_correctly() {
+        let Itau::SeuNumero {
+            carteira,
+            nosso_numero,
+            seu_numero,
+            codigo_cliente,
+            dac,
+        } = Itau::new(b"1981234567811089541234550").unwrap() else {
+            panic!("Deveria ser o layout com seu número");
+        };
+
+        assert_eq!(carteira.as_str(), "198");
+        assert_eq!(nosso_numero.as_str(), "12345678");
+        assert_eq!(seu_numero.as_str(), "1108954");
+        assert_eq!(codigo_cliente.as_str(), "12345");
+        assert_eq!(dac, 5);
+    }
+
+    #[test]
+    fn encode_round_trip() {
+        let campos_livres = [
+            b"1101234567880057123457000",
+            b"1261234567850057123457000",
+            b"1981234567811089541234550",
+        ];
+
+        for campo_livre in campos_livres {
+            assert_eq!(&Itau::new(campo_livre).unwrap().encode().unwrap(), campo_livre);
+        }
+    }
+
+    #[test]
+    fn invalid_dac() {
+        let campos_livres = [
+            b"1101234567870057123457000",
+            b"1101234567880057123456000",
+            b"1261234567840057123457000",
+            b"1981234567811089541234560",
+        ];
+
+        for campo_livre in campos_livres {
+            assert!(matches!(
+                Itau::new(campo_livre),
+                Err(BoletoError::InvalidDigitoVerificadorCampoLivre),
+            ));
+        }
+    }
+}
@@ -0,0 +1,125 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{CampoLivre, CampoLivreEncoder, Digitos};
+use crate::utils::dv_utils;
+use crate::BoletoError;
+
+/// Campo livre do Santander (033)
+///
+/// ```text
+/// 00000000001111111111222222
+/// 01234567890123456789012345
+/// 9BBBBBBBNNNNNNNNNNNNDIKKK
+/// ```
+///
+/// - 9: fixo
+/// - B: código do beneficiário padrão Santander
+/// - N: nosso número
+/// - D: DV do nosso número (módulo 11)
+/// - I: IOF (somente para seguradoras, que usam 7, 8 ou 9; demais clientes
+///   usam 0)
+/// - K: tipo de modalidade da carteira (101 - Cobrança Rápida com Registro,
+///   104 - Cobrança Eletrônica com Registro)
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Santander {
+    pub codigo_beneficiario: Digitos<7>,
+    /// Nosso número com o DV na última posição
+    pub nosso_numero: Digitos<13>,
+    pub dv_nosso_numero: u8,
+    pub iof: u8,
+    pub carteira: Digitos<3>,
+}
+
+/// Valores aceitos na posição do IOF
+const IOF_VALIDOS: [u8; 4] = [0, 7, 8, 9];
+
+impl Santander {
+    pub(crate) fn new(campo_livre: &[u8]) -> Result<Self, BoletoError> {
+        if campo_livre[0] != b'9' || !IOF_VALIDOS.contains(&(campo_livre[21] - b'0')) {
+            return Err(BoletoError::InvalidCampoLivre);
+        }
+
+        // Restos 0 e 1 resultam em 0, resto 10 resulta em 1
+        let dv_nosso_numero = dv_utils::mod_11(campo_livre[8..20].iter()).unwrap_or(b'0');
+
+        if dv_nosso_numero != campo_livre[20] {
+            return Err(BoletoError::InvalidDigitoVerificadorCampoLivre);
+        }
+
+        Ok(Self {
+            codigo_beneficiario: Digitos::new(&campo_livre[1..8]),
+            nosso_numero: Digitos::new(&campo_livre[8..21]),
+            dv_nosso_numero: dv_nosso_numero - b'0',
+            iof: campo_livre[21] - b'0',
+            carteira: Digitos::new(&campo_livre[22..25]),
+        })
+    }
+}
+
+impl CampoLivreEncoder for Santander {
+    fn encode(&self) -> Result<[u8; CampoLivre::LENGTH], BoletoError> {
+        if !IOF_VALIDOS.contains(&self.iof) {
+            return Err(BoletoError::InvalidCampoLivre);
+        }
+
+        let mut campo_livre = [b'0'; CampoLivre::LENGTH];
+
+        campo_livre[0] = b'9';
+        campo_livre[1..8].copy_from_slice(self.codigo_beneficiario.as_bytes());
+        campo_livre[8..20].copy_from_slice(&self.nosso_numero.as_bytes()[..12]);
+        campo_livre[20] = dv_utils::mod_11(campo_livre[8..20].iter()).unwrap_or(b'0');
+        campo_livre[21] = self.iof + b'0';
+        campo_livre[22..25].copy_from_slice(self.carteira.as_bytes());
+
+        Ok(campo_livre)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_campo_livre_correctly() {
+        let campo_livre = Santander::new(b"9028203356661245780020101").unwrap();
+
+        assert_eq!(campo_livre.codigo_beneficiario.as_str(), "0282033");
+        assert_eq!(campo_livre.nosso_numero.as_str(), "5666124578002");
+        assert_eq!(campo_livre.dv_nosso_numero, 2);
+        assert_eq!(campo_livre.iof, 0);
+        assert_eq!(campo_livre.carteira.as_str(), "101");
+        assert_eq!(&campo_livre.encode().unwrap(), b"9028203356661245780020101");
+    }
+
+    #[test]
+    fn invalid_campo_livre() {
+        assert!(matches!(
+            Santander::new(b"8028203356661245780020101"),
+            Err(BoletoError::InvalidCampoLivre),
+        ));
+        assert!(matches!(
+            Santander::new(b"9028203356661245780030101"),
+            Err(BoletoError::InvalidDigitoVerificadorCampoLivre),
+        ));
+        // IOF fora de 0, 7, 8 ou 9
+        assert!(matches!(
+            Santander::new(b"9028203356661245780025101"),
+            Err(BoletoError::InvalidCampoLivre),
+        ));
+    }
+
+    #[test]
+    fn encode_iof() {
+        let mut campo_livre = Santander::new(b"9028203356661245780020101").unwrap();
+
+        campo_livre.iof = 7;
+        assert_eq!(&campo_livre.encode().unwrap(), b"9028203356661245780027101");
+
+        for iof in [1, 6, 10, 255] {
+            campo_livre.iof = iof;
+            assert!(matches!(campo_livre.encode(), Err(BoletoError::InvalidCampoLivre)));
+        }
+    }
+}
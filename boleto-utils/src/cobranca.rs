@@ -1,16 +1,39 @@
-use std::fmt;
-
-use chrono::NaiveDate;
-use serde::Serialize;
-
-use crate::utils::{dv_utils, fator_vencimento_to_date, u8_array_to_u16};
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::de::value::MapAccessDeserializer;
+#[cfg(feature = "serde")]
+use serde::de::{self, MapAccess, Visitor};
+#[cfg(feature = "serde")]
+use serde::ser::SerializeMap;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+use crate::bancos::{self, Banco};
+#[cfg(feature = "std")]
+use crate::campo_livre::{CampoLivre, Decoders};
+#[cfg(feature = "std")]
+use crate::diagnostico::{validar_estrutura, Campo, Diagnostic};
+#[cfg(feature = "std")]
+use crate::fator_vencimento::FatorVencimentoPolicy;
+use crate::fator_vencimento::{fator_vencimento_to_date, Data};
+#[cfg(feature = "std")]
+use crate::render::itf::{self, Itf};
+#[cfg(feature = "serde")]
+use crate::utils::serde_utils::{conferir, presente};
+use crate::utils::{dv_utils, u8_array_to_u16};
+use crate::valor::Valor;
 use crate::BoletoError;
 
-pub struct CodBarras([u8; Cobranca::COD_BARRAS_LENGTH]);
+#[derive(PartialEq, Eq)]
+pub struct CodBarras([u8; CodBarras::LENGTH]);
 
 impl CodBarras {
+    pub const LENGTH: usize = 44;
+
     pub fn new(input: &[u8]) -> Result<Self, BoletoError> {
-        if input.len() != Cobranca::COD_BARRAS_LENGTH {
+        if input.len() != Self::LENGTH {
             return Err(BoletoError::InvalidLength);
         }
 
@@ -23,14 +46,14 @@ impl CodBarras {
             return Err(BoletoError::NumbersOnly);
         }
 
-        let mut cod_barras = [0u8; Cobranca::COD_BARRAS_LENGTH];
+        let mut cod_barras = [0u8; Self::LENGTH];
         cod_barras.copy_from_slice(input);
 
         Ok(Self(cod_barras))
     }
 
     pub fn as_str(&self) -> &str {
-        unsafe { std::str::from_utf8_unchecked(&self.0) }
+        unsafe { core::str::from_utf8_unchecked(&self.0) }
     }
 
     pub fn as_bytes(&self) -> &[u8] {
@@ -55,9 +78,84 @@ impl CodBarras {
         )
     }
 
+    pub fn cod_banco(&self) -> CodBanco {
+        CodBanco(u8_array_to_u16(&self[0..3]))
+    }
+
+    pub fn campo_livre(&self) -> &[u8] {
+        &self[19..44]
+    }
+
     pub fn update_dv(&mut self) {
         self.0[4] = self.calculate_dv() + b'0';
     }
+
+    pub fn cod_moeda(&self) -> Result<CodigoMoeda, BoletoError> {
+        match self[3] {
+            b'9' => Ok(CodigoMoeda::Real),
+            b'0' => Ok(CodigoMoeda::Outras),
+            _ => Err(BoletoError::InvalidCodigoMoeda),
+        }
+    }
+
+    pub fn fator_vencimento(&self) -> u16 {
+        u8_array_to_u16(&self[5..9])
+    }
+
+    /// Data de vencimento segundo a política padrão de fator de vencimento
+    pub fn data_vencimento(&self) -> Option<Data> {
+        fator_vencimento_to_date(self.fator_vencimento())
+    }
+
+    pub fn valor(&self) -> Option<Valor> {
+        match Valor::from_digits(&self[9..19]).expect("10 dígitos sempre cabem em u64") {
+            valor if valor.is_zero() => None,
+            valor => Some(valor),
+        }
+    }
+
+    /// Valida o código da moeda, o fator de vencimento e o dígito verificador
+    /// geral, sem consultar os dados dos bancos (campo livre)
+    pub fn validate(&self) -> Result<(), BoletoError> {
+        self.cod_moeda()?;
+
+        if self.fator_vencimento() > 0 && self.data_vencimento().is_none() {
+            return Err(BoletoError::InvalidFatorVencimento);
+        }
+
+        if self.calculate_dv() != self[4] - b'0' {
+            return Err(BoletoError::InvalidDigitoVerificadorGeral);
+        }
+
+        Ok(())
+    }
+
+    /// Lê um código de barras ou uma linha digitável, validando os dígitos
+    /// verificadores e o fator de vencimento (ver `validate`), mas não o
+    /// campo livre. Disponível também sem `std`.
+    pub fn parse(value: &[u8]) -> Result<Self, BoletoError> {
+        if value.len() == LinhaDigitavel::LENGTH {
+            let linha_digitavel = LinhaDigitavel::new(value)?;
+            linha_digitavel.validate()?;
+
+            return Ok(Self::from(&linha_digitavel));
+        }
+
+        let cod_barras = Self::new(value)?;
+        cod_barras.validate()?;
+
+        Ok(cod_barras)
+    }
+
+    /// SVG do código de barras no tamanho físico (ver `render::svg`),
+    /// opcionalmente com a linha digitável formatada abaixo das barras
+    #[cfg(feature = "std")]
+    pub fn to_svg(&self, config: itf::Config, linha_digitavel: bool) -> Result<String, BoletoError> {
+        let itf = Itf::new(self, config)?;
+        let legenda = linha_digitavel.then(|| LinhaDigitavel::from(self).formatted());
+
+        Ok(itf.to_svg(legenda.as_deref()))
+    }
 }
 
 impl From<&LinhaDigitavel> for CodBarras {
@@ -72,7 +170,7 @@ impl From<&LinhaDigitavel> for CodBarras {
         // AAABKUUUUVVVVVVVVVVCCCCCDDDDDDDDDDEEEEEEEEEE
 
         let LinhaDigitavel(src) = linha_digitavel;
-        let mut barcode = [0_u8; Cobranca::COD_BARRAS_LENGTH];
+        let mut barcode = [0_u8; CodBarras::LENGTH];
 
         barcode[0..4].copy_from_slice(&src[0..4]);
         barcode[4..19].copy_from_slice(&src[32..47]);
@@ -84,10 +182,10 @@ impl From<&LinhaDigitavel> for CodBarras {
     }
 }
 
-impl std::fmt::Debug for CodBarras {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl fmt::Debug for CodBarras {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("CodBarras")
-            .field(unsafe { &std::str::from_utf8_unchecked(&self.0)})
+            .field(unsafe { &core::str::from_utf8_unchecked(&self.0)})
             .finish()
     }
 }
@@ -98,14 +196,15 @@ impl fmt::Display for CodBarras {
     }
 }
 
-impl std::ops::Deref for CodBarras {
-    type Target = [u8; Cobranca::COD_BARRAS_LENGTH];
+impl core::ops::Deref for CodBarras {
+    type Target = [u8; CodBarras::LENGTH];
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
+#[cfg(feature = "serde")]
 impl Serialize for CodBarras {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where
@@ -114,11 +213,25 @@ impl Serialize for CodBarras {
     }
 }
 
-pub struct LinhaDigitavel([u8; Cobranca::LINHA_DIGITAVEL_LENGTH]);
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CodBarras {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de> {
+        let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+        Self::new(value.as_bytes()).map_err(de::Error::custom)
+    }
+}
+
+#[derive(PartialEq, Eq)]
+pub struct LinhaDigitavel([u8; LinhaDigitavel::LENGTH]);
 
 impl LinhaDigitavel {
+    pub const LENGTH: usize = 47;
+
     pub fn new(input: &[u8]) -> Result<Self, BoletoError> {
-        if input.len() != Cobranca::LINHA_DIGITAVEL_LENGTH {
+        if input.len() != Self::LENGTH {
             return Err(BoletoError::InvalidLength);
         }
 
@@ -131,14 +244,36 @@ impl LinhaDigitavel {
             return Err(BoletoError::NumbersOnly);
         }
 
-        let mut linha_digitavel = [0u8; Cobranca::LINHA_DIGITAVEL_LENGTH];
+        let mut linha_digitavel = [0u8; Self::LENGTH];
         linha_digitavel.copy_from_slice(input);
 
         Ok(Self(linha_digitavel))
     }
 
     pub fn as_str(&self) -> &str {
-        unsafe { std::str::from_utf8_unchecked(&self.0) }
+        unsafe { core::str::from_utf8_unchecked(&self.0) }
+    }
+
+    /// Linha digitável no formato impresso no boleto:
+    /// `AAABC.CCCCX DDDDD.DDDDDY EEEEE.EEEEEZ K UUUUVVVVVVVVVV`
+    #[cfg(feature = "std")]
+    pub fn formatted(&self) -> String {
+        format!("{self:#}")
+    }
+
+    /// Valida os dígitos verificadores dos campos e o código de barras
+    /// correspondente (ver `CodBarras::validate`)
+    pub fn validate(&self) -> Result<(), BoletoError> {
+        let cod_barras = CodBarras::from(self);
+        cod_barras.validate()?;
+
+        let (dv1, dv2, dv3) = cod_barras.calculate_dv_campos();
+
+        if (self[9], self[20], self[31]) != (dv1, dv2, dv3) {
+            return Err(BoletoError::InvalidDigitoVerificadorCampos);
+        }
+
+        Ok(())
     }
 }
 
@@ -155,7 +290,7 @@ impl From<&CodBarras> for LinhaDigitavel {
 
         let CodBarras(src) = cod_barras;
 
-        let mut digitable_line = [0_u8; Cobranca::LINHA_DIGITAVEL_LENGTH];
+        let mut digitable_line = [0_u8; LinhaDigitavel::LENGTH];
         let (dv1, dv2, dv3) = cod_barras.calculate_dv_campos();
 
         // Campo 1
@@ -181,28 +316,40 @@ impl From<&CodBarras> for LinhaDigitavel {
     }
 }
 
-impl std::fmt::Debug for LinhaDigitavel {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl fmt::Debug for LinhaDigitavel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("LinhaDigitavel")
-            .field(unsafe { &std::str::from_utf8_unchecked(&self.0)})
+            .field(unsafe { &core::str::from_utf8_unchecked(&self.0)})
             .finish()
     }
 }
 
+/// Com `{:#}` usa o formato impresso no boleto (ver `formatted`)
 impl fmt::Display for LinhaDigitavel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(self.as_str())
+        if !f.alternate() {
+            return f.write_str(self.as_str());
+        }
+
+        let s = self.as_str();
+
+        write!(
+            f,
+            "{}.{} {}.{} {}.{} {} {}",
+            &s[0..5], &s[5..10], &s[10..15], &s[15..21], &s[21..26], &s[26..32], &s[32..33], &s[33..47],
+        )
     }
 }
 
-impl std::ops::Deref for LinhaDigitavel {
-    type Target = [u8; Cobranca::LINHA_DIGITAVEL_LENGTH];
+impl core::ops::Deref for LinhaDigitavel {
+    type Target = [u8; LinhaDigitavel::LENGTH];
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
+#[cfg(feature = "serde")]
 impl Serialize for LinhaDigitavel {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where
@@ -211,18 +358,42 @@ impl Serialize for LinhaDigitavel {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for LinhaDigitavel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de> {
+        let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+        Self::new(value.as_bytes()).map_err(de::Error::custom)
+    }
+}
+
+/// Serializa a linha digitável junto com a versão formatada, para ser usado
+/// com `#[serde(flatten)]`
+#[cfg(feature = "serde")]
+fn serialize_linha_digitavel<S>(linha_digitavel: &LinhaDigitavel, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer {
+    let mut map = serializer.serialize_map(Some(2))?;
+    map.serialize_entry("linha_digitavel", linha_digitavel.as_str())?;
+    map.serialize_entry("linha_digitavel_formatada", &linha_digitavel.formatted())?;
+    map.end()
+}
+
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CodigoMoeda {
     Real,
     Outras,
 }
 
-impl Into<u8> for CodigoMoeda{
-    fn into(self) -> u8 {
-        match self {
-            Self::Real => b'9',
-            Self::Outras => b'0',
+impl From<CodigoMoeda> for u8 {
+    fn from(cod_moeda: CodigoMoeda) -> u8 {
+        match cod_moeda {
+            CodigoMoeda::Real => b'9',
+            CodigoMoeda::Outras => b'0',
         }
     }
 }
@@ -236,30 +407,51 @@ impl fmt::Display for CodigoMoeda {
     }
 }
 
-#[derive(Debug, Serialize, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CodBanco(pub u16);
 
+#[cfg(feature = "std")]
+impl CodBanco {
+    pub fn banco(&self) -> Option<&'static Banco> {
+        bancos::get(*self)
+    }
+}
+
 impl fmt::Display for CodBanco {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:03}", self.0)
     }
 }
 
-#[derive(Debug, Serialize)]
-#[serde(rename = "cobranca")]
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "cobranca"))]
 pub struct Cobranca {
     pub cod_barras: CodBarras,
+    #[cfg_attr(feature = "serde", serde(flatten, serialize_with = "serialize_linha_digitavel"))]
     pub linha_digitavel: LinhaDigitavel,
     pub cod_banco: CodBanco,
+    pub info_banco: Option<&'static Banco>,
     pub cod_moeda: CodigoMoeda,
-    #[serde(skip)]
     pub digito_verificador: u8,
-    #[serde(skip)]
     pub fator_vencimento: u16,
-    pub data_vencimento: Option<NaiveDate>,
-    pub valor: Option<f64>,
+    /// Data candidata mais próxima da data de referência da política usada
+    pub data_vencimento: Option<Data>,
+    /// Todas as datas plausíveis, preenchido apenas quando o fator é ambíguo
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub datas_vencimento_possiveis: Vec<Data>,
+    pub valor: Option<Valor>,
+    pub campo_livre: Option<CampoLivre>,
+    /// Problema na decodificação do campo livre pelo layout do banco. Nesse
+    /// caso `campo_livre` é `None`, mas o boleto continua válido pelo padrão
+    /// Febraban.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub campo_livre_error: Option<Diagnostic>,
 }
 
+#[cfg(feature = "std")]
 impl fmt::Display for Cobranca {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -275,99 +467,279 @@ impl fmt::Display for Cobranca {
             ),
             self.cod_barras,
             self.linha_digitavel,
-            self.cod_banco,
+            match self.info_banco {
+                Some(banco) => format!("{banco}"),
+                None => format!("{}", self.cod_banco),
+            },
             self.cod_moeda,
             match self.valor {
-                Some(v) => format!("{v:.2}"),
+                Some(v) => format!("{v}"),
                 None => "Sem valor".to_owned(),
             },
-            match self.data_vencimento {
-                Some(date) => format!("{date}"),
-                None => "Sem vencimento".to_owned(),
+            match (self.data_vencimento, self.datas_vencimento_possiveis.as_slice()) {
+                (Some(date), []) => format!("{date}"),
+                (Some(date), possiveis) => format!(
+                    "{date} (ambígua: {})",
+                    possiveis.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(", "),
+                ),
+                (None, _) => "Sem vencimento".to_owned(),
             },
         )
     }
 }
 
+/// Forma serializada de `Cobranca`. Apenas o código de barras é obrigatório,
+/// os demais campos são conferidos com os calculados a partir dele.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct CobrancaSerializada {
+    cod_barras: CodBarras,
+    linha_digitavel: Option<LinhaDigitavel>,
+    linha_digitavel_formatada: Option<String>,
+    cod_banco: Option<CodBanco>,
+    #[serde(default, deserialize_with = "presente")]
+    info_banco: Option<Option<Banco>>,
+    cod_moeda: Option<CodigoMoeda>,
+    digito_verificador: Option<u8>,
+    fator_vencimento: Option<u16>,
+    #[serde(default, deserialize_with = "presente")]
+    data_vencimento: Option<Option<Data>>,
+    #[serde(default)]
+    datas_vencimento_possiveis: Vec<Data>,
+    #[serde(default, deserialize_with = "presente")]
+    valor: Option<Option<Valor>>,
+    #[serde(default, deserialize_with = "presente")]
+    campo_livre: Option<Option<CampoLivre>>,
+}
+
+#[cfg(feature = "serde")]
+impl CobrancaSerializada {
+    fn validar<E: de::Error>(self) -> Result<Cobranca, E> {
+        let mut cobranca = Cobranca::new(self.cod_barras.as_bytes()).map_err(E::custom)?;
+
+        conferir("linha_digitavel", self.linha_digitavel.as_ref(), &cobranca.linha_digitavel)?;
+        conferir("linha_digitavel_formatada", self.linha_digitavel_formatada, cobranca.linha_digitavel.formatted())?;
+        conferir("cod_banco", self.cod_banco, cobranca.cod_banco)?;
+        conferir("info_banco", self.info_banco.as_ref().map(Option::as_ref), cobranca.info_banco)?;
+        conferir("cod_moeda", self.cod_moeda, cobranca.cod_moeda)?;
+        conferir("digito_verificador", self.digito_verificador, cobranca.digito_verificador)?;
+        conferir("fator_vencimento", self.fator_vencimento, cobranca.fator_vencimento)?;
+        conferir("valor", self.valor, cobranca.valor)?;
+        conferir("campo_livre", self.campo_livre.as_ref().map(Option::as_ref), cobranca.campo_livre.as_ref())?;
+
+        // As datas dependem da política de fator de vencimento usada na
+        // serialização, então basta que estejam entre todas as datas que o
+        // fator do código de barras pode representar
+        match self.data_vencimento {
+            Some(data_vencimento) => {
+                let todas = FatorVencimentoPolicy::irrestrita().candidatas(cobranca.fator_vencimento);
+                let possiveis = &self.datas_vencimento_possiveis;
+
+                let datas_conferem = match data_vencimento {
+                    None => cobranca.fator_vencimento == 0 && possiveis.is_empty(),
+                    Some(data) => todas.contains(&data)
+                        && (possiveis.is_empty()
+                            || possiveis.len() >= 2
+                                && possiveis.contains(&data)
+                                && possiveis.windows(2).all(|par| par[0] < par[1])
+                                && possiveis.iter().all(|data| todas.contains(data))),
+                };
+
+                if !datas_conferem {
+                    return Err(E::custom("campo `data_vencimento` não corresponde ao código de barras"));
+                }
+
+                cobranca.data_vencimento = data_vencimento;
+                cobranca.datas_vencimento_possiveis = self.datas_vencimento_possiveis;
+            }
+            None if !self.datas_vencimento_possiveis.is_empty() => {
+                return Err(E::custom("campo `datas_vencimento_possiveis` informado sem `data_vencimento`"));
+            }
+            None => {}
+        }
 
+        Ok(cobranca)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct CobrancaVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for CobrancaVisitor {
+    type Value = Cobranca;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("código de barras, linha digitável ou boleto de cobrança serializado")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Cobranca::new(value.as_bytes()).map_err(E::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        CobrancaSerializada::deserialize(MapAccessDeserializer::new(map))?.validar()
+    }
+}
+
+/// Aceita o código de barras, a linha digitável ou a forma serializada, que é
+/// validada novamente a partir do código de barras
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Cobranca {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de> {
+        deserializer.deserialize_any(CobrancaVisitor)
+    }
+}
+
+
+#[cfg(feature = "std")]
 impl Cobranca {
-    pub const COD_BARRAS_LENGTH: usize = 44;
-    pub const LINHA_DIGITAVEL_LENGTH: usize = 47;
+    pub const COD_BARRAS_LENGTH: usize = CodBarras::LENGTH;
+    pub const LINHA_DIGITAVEL_LENGTH: usize = LinhaDigitavel::LENGTH;
 
     pub fn new(value: &[u8]) -> Result<Self, BoletoError> {
-        let (cod_barras, linha_digitavel): (CodBarras, LinhaDigitavel) = match value.len() {
-            Self::COD_BARRAS_LENGTH => {
-                let cod_barras = CodBarras::new(value)?;
-                let linha_digitavel = LinhaDigitavel::from(&cod_barras);
-                (cod_barras, linha_digitavel)
-            },
-            Self::LINHA_DIGITAVEL_LENGTH => {
-                let linha_digitavel = LinhaDigitavel::new(value)?;
-                ((&linha_digitavel).into(), linha_digitavel)
-            },
-            _ => return Err(BoletoError::InvalidLength),
-        };
+        Self::new_with_policy(value, &FatorVencimentoPolicy::default())
+    }
+
+    /// Assim como `new`, mas resolve o fator de vencimento de acordo com a
+    /// política informada. Fatores sem nenhuma data dentro da janela da
+    /// política são considerados inválidos.
+    pub fn new_with_policy(value: &[u8], policy: &FatorVencimentoPolicy) -> Result<Self, BoletoError> {
+        Self::diagnose_with_policy(value, policy).map_err(|mut diagnostics| diagnostics.remove(0).erro)
+    }
+
+    /// Assim como `new`, mas retorna todos os problemas encontrados em vez de
+    /// apenas o primeiro
+    pub fn diagnose(value: &[u8]) -> Result<Self, Vec<Diagnostic>> {
+        Self::diagnose_with_policy(value, &FatorVencimentoPolicy::default())
+    }
 
-        let cod_banco = CodBanco(u8_array_to_u16(&cod_barras[0..3]));
+    pub fn diagnose_with_policy(value: &[u8], policy: &FatorVencimentoPolicy) -> Result<Self, Vec<Diagnostic>> {
+        Self::diagnose_with_decoders(value, policy, None)
+    }
 
-        let cod_moeda = match cod_barras[3] {
-            b'9' => CodigoMoeda::Real,
-            b'0' => CodigoMoeda::Outras,
-            _ => return Err(BoletoError::InvalidCodigoMoeda),
+    /// Assim como `new`, mas decodifica o campo livre com os decodificadores
+    /// informados em vez do registro global
+    pub fn new_with(decoders: &Decoders, value: &[u8]) -> Result<Self, BoletoError> {
+        Self::diagnose_with_decoders(value, &FatorVencimentoPolicy::default(), Some(decoders))
+            .map_err(|mut diagnostics| diagnostics.remove(0).erro)
+    }
+
+    fn diagnose_with_decoders(
+        value: &[u8],
+        policy: &FatorVencimentoPolicy,
+        decoders: Option<&Decoders>,
+    ) -> Result<Self, Vec<Diagnostic>> {
+        validar_estrutura(value, &[Self::COD_BARRAS_LENGTH, Self::LINHA_DIGITAVEL_LENGTH], false)?;
+
+        let is_linha_digitavel = value.len() == Self::LINHA_DIGITAVEL_LENGTH;
+
+        let (cod_barras, linha_digitavel): (CodBarras, LinhaDigitavel) = if is_linha_digitavel {
+            let linha_digitavel = LinhaDigitavel::new(value).expect("estrutura já validada");
+            ((&linha_digitavel).into(), linha_digitavel)
+        } else {
+            let cod_barras = CodBarras::new(value).expect("estrutura já validada");
+            let linha_digitavel = LinhaDigitavel::from(&cod_barras);
+            (cod_barras, linha_digitavel)
         };
 
-        let fator_vencimento: u16 = u8_array_to_u16(&cod_barras[5..9]);
+        // Posições de cada informação na entrada
+        let (pos_dv, pos_fator, pos_campo_livre) = if is_linha_digitavel {
+            (32, 33..37, 4..32)
+        } else {
+            (4, 5..9, 19..44)
+        };
 
-        if fator_vencimento > 0 && fator_vencimento < 1000 {
-            return Err(BoletoError::InvalidFatorVencimento);
+        let mut diagnostics = Vec::new();
+
+        let cod_banco = cod_barras.cod_banco();
+
+        let cod_moeda = cod_barras.cod_moeda().map_err(|erro| {
+            diagnostics.push(Diagnostic::new(Campo::CodigoMoeda, value, 3..4, erro));
+        }).ok();
+
+        let fator_vencimento = cod_barras.fator_vencimento();
+
+        let mut datas_vencimento_possiveis = policy.candidatas(fator_vencimento);
+        let data_vencimento = policy.resolve(fator_vencimento);
+
+        if fator_vencimento > 0 && data_vencimento.is_none() {
+            diagnostics.push(Diagnostic::new(Campo::FatorVencimento, value, pos_fator, BoletoError::InvalidFatorVencimento));
         }
 
-        let valor = {
-            let x = unsafe { std::str::from_utf8_unchecked(&cod_barras[9..19]) };
-            match  x.parse::<f64>().unwrap()
-            {
-                x if x.is_normal() => Some(x / 100.00),
-                _ => None,
-            }
-        };
+        if datas_vencimento_possiveis.len() < 2 {
+            datas_vencimento_possiveis.clear();
+        }
 
-        let digito_verificador: u8 = {
-            let dv = cod_barras.calculate_dv();
+        let valor = cod_barras.valor();
 
-            if dv != cod_barras[4] - b'0' {
-                return Err(BoletoError::InvalidDigitoVerificadorGeral);
-            }
+        let digito_verificador = cod_barras.calculate_dv();
 
-            dv
-        };
+        if digito_verificador != cod_barras[4] - b'0' {
+            diagnostics.push(Diagnostic::dv(
+                Campo::DvGeral,
+                pos_dv..pos_dv + 1,
+                digito_verificador + b'0',
+                cod_barras[4],
+                BoletoError::InvalidDigitoVerificadorGeral,
+            ));
+        }
 
         {
             let dvs = cod_barras.calculate_dv_campos();
 
-            if linha_digitavel[9] != dvs.0
-                || linha_digitavel[20] != dvs.1
-                || linha_digitavel[31] != dvs.2
-            {
-                return Err(BoletoError::InvalidDigitoVerificadorCampos);
+            for (i, (campo, dv)) in [(0..10, dvs.0), (10..21, dvs.1), (21..32, dvs.2)].into_iter().enumerate() {
+                let encontrado = linha_digitavel[campo.end - 1];
+
+                if encontrado != dv {
+                    // Na entrada como código de barras os DVs dos campos são sempre
+                    // calculados, então só a linha digitável pode chegar aqui
+                    diagnostics.push(Diagnostic::dv(
+                        Campo::linha_digitavel(i),
+                        campo,
+                        dv,
+                        encontrado,
+                        BoletoError::InvalidDigitoVerificadorCampos,
+                    ));
+                }
             }
         };
 
-        Ok(Self {
-            cod_barras,
-            linha_digitavel,
-            cod_banco,
-            cod_moeda,
-            fator_vencimento,
-            digito_verificador,
-            data_vencimento: fator_vencimento_to_date(fator_vencimento),
-            valor,
-        })
+        let campo_livre = match decoders {
+            Some(decoders) => decoders.decode(&cod_barras),
+            None => CampoLivre::new(&cod_barras),
+        };
+        let (campo_livre, campo_livre_error) = match campo_livre {
+            Ok(campo_livre) => (campo_livre, None),
+            Err(erro) => (None, Some(Diagnostic::new(Campo::CampoLivre, value, pos_campo_livre, erro))),
+        };
+
+        match cod_moeda {
+            Some(cod_moeda) if diagnostics.is_empty() => Ok(Self {
+                cod_barras,
+                linha_digitavel,
+                cod_banco,
+                info_banco: cod_banco.banco(),
+                cod_moeda,
+                fator_vencimento,
+                digito_verificador,
+                data_vencimento,
+                datas_vencimento_possiveis,
+                valor,
+                campo_livre,
+                campo_livre_error,
+            }),
+            _ => Err(diagnostics),
+        }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
+    use crate::fator_vencimento::DataCivil;
 
     #[test]
     fn get_cod_banco_correctly() {
@@ -390,6 +762,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn get_info_banco_correctly() {
+        let cobranca = Cobranca::new(b"30195917700001452780000000002310237287225104").unwrap();
+        let banco = cobranca.info_banco.unwrap();
+
+        assert_eq!(banco.id, 301);
+        assert_eq!(banco.nome, "BPP Instituição de Pagamento S.A.");
+        assert!(
+            cobranca.to_string().contains("Banco: [301] BPP Instituição de Pagamento S.A."),
+        );
+
+        let cobranca = Cobranca::new(b"99996444455555555556666666666666666666666666").unwrap();
+
+        assert!(cobranca.info_banco.is_none());
+        assert!(cobranca.to_string().contains("Banco: 999\n"));
+    }
+
+    #[test]
+    fn get_campo_livre_correctly() {
+        let cobranca = Cobranca::new(b"00191667900002434790000002656973019362470618").unwrap();
+
+        assert!(matches!(cobranca.campo_livre, Some(CampoLivre::BancoDoBrasil(_))));
+        assert_eq!(cobranca.campo_livre.unwrap().nosso_numero(), "26569730193624706");
+
+        let cobranca = Cobranca::new(b"34196166700000123451101234567880057123457000").unwrap();
+
+        assert!(matches!(cobranca.campo_livre, Some(CampoLivre::Itau(_))));
+        assert_eq!(cobranca.campo_livre.unwrap().nosso_numero(), "12345678");
+
+        let cobranca = Cobranca::new(b"99996444455555555556666666666666666666666666").unwrap();
+
+        assert!(cobranca.campo_livre.is_none());
+    }
+
     #[test]
     fn get_cod_moeda_correctly() {
         match Cobranca::new(b"11191444455555555556666666666666666666666666") {
@@ -424,57 +830,57 @@ mod tests {
             (
                 b"11199100055555555556666666666666666666666666",
                 1000,
-                Some(NaiveDate::from_ymd_opt(2025, 2, 22).unwrap()),
+                Some(Data::new(2025, 2, 22).unwrap()),
             ),
             (
                 b"11191100255555555556666666666666666666666666",
                 1002,
-                Some(NaiveDate::from_ymd_opt(2025, 2, 24).unwrap()),
+                Some(Data::new(2025, 2, 24).unwrap()),
             ),
             (
                 b"11196166755555555556666666666666666666666666",
                 1667,
-                Some(NaiveDate::from_ymd_opt(2026, 12, 21).unwrap()),
+                Some(Data::new(2026, 12, 21).unwrap()),
             ),
             (
                 b"11198478955555555556666666666666666666666666",
                 4789,
-                Some(NaiveDate::from_ymd_opt(2010, 11, 17).unwrap()),
+                Some(Data::new(2010, 11, 17).unwrap()),
             ),
             (
                 b"11193999955555555556666666666666666666666666",
                 9999,
-                Some(NaiveDate::from_ymd_opt(2025, 2, 21).unwrap()),
+                Some(Data::new(2025, 2, 21).unwrap()),
             ),
             (
                 b"75696903800002500001434301033723400014933001",
                 9038,
-                Some(NaiveDate::from_ymd_opt(2022, 7, 6).unwrap()),
+                Some(Data::new(2022, 7, 6).unwrap()),
             ),
             (
                 b"00191667900002434790000002656973019362470618",
                 6679,
-                Some(NaiveDate::from_ymd_opt(2016, 1, 20).unwrap()),
+                Some(Data::new(2016, 1, 20).unwrap()),
             ),
             (
                 b"00195586200000773520000002464206011816073018",
                 5862,
-                Some(NaiveDate::from_ymd_opt(2013, 10, 25).unwrap()),
+                Some(Data::new(2013, 10, 25).unwrap()),
             ),
             (
                 b"75592896700003787000003389850761252543475984",
                 8967,
-                Some(NaiveDate::from_ymd_opt(2022, 4, 26).unwrap()),
+                Some(Data::new(2022, 4, 26).unwrap()),
             ),
             (
                 b"23791672000003249052028269705944177105205220",
                 6720,
-                Some(NaiveDate::from_ymd_opt(2016, 3, 1).unwrap()),
+                Some(Data::new(2016, 3, 1).unwrap()),
             ),
             (
                 b"23791672000003097902028060007024617500249000",
                 6720,
-                Some(NaiveDate::from_ymd_opt(2016, 3, 1).unwrap()),
+                Some(Data::new(2016, 3, 1).unwrap()),
             ),
         ];
         for (barcode, expected_fator, expected_date) in barcodes {
@@ -505,16 +911,37 @@ mod tests {
 
     }
 
+    #[test]
+    fn get_data_vencimento_with_policy_correctly() {
+        let barcode = b"11192446855555555556666666666666666666666666".as_slice();
+        let date = |y, m, d| Data::new(y, m, d).unwrap();
+
+        let result = Cobranca::new(barcode).unwrap();
+        assert_eq!(result.data_vencimento, Some(date(2034, 8, 22)));
+        assert!(result.datas_vencimento_possiveis.is_empty());
+
+        let policy = FatorVencimentoPolicy::new(date(2020, 1, 1), 6000, 6000);
+        let result = Cobranca::new_with_policy(barcode, &policy).unwrap();
+        assert_eq!(result.data_vencimento, Some(date(2009, 12, 31)));
+        assert_eq!(result.datas_vencimento_possiveis, vec![date(2009, 12, 31), date(2034, 8, 22)]);
+
+        let policy = FatorVencimentoPolicy::new(date(2025, 2, 22), 30, 30);
+        assert!(matches!(
+            Cobranca::new_with_policy(barcode, &policy),
+            Err(BoletoError::InvalidFatorVencimento),
+        ));
+    }
+
     #[test]
     fn get_valor_correctly() {
         let barcodes = [
             (
                 b"11191444455555555556666666666666666666666666",
-                Some(55555555.55_f64),
+                Some(Valor::from_centavos(5_555_555_555)),
             ),
             (
                 b"11196444499999999996666666666666666666666666",
-                Some(99999999.99),
+                Some(Valor::from_centavos(9_999_999_999)),
             ),
             (b"11193444400000000006666666666666666666666666", None),
         ];
@@ -552,6 +979,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn format_linha_digitavel_correctly() {
+        let linha_digitavel = LinhaDigitavel::new(b"75691434360103372340200149330011690380000250000").unwrap();
+        let expected = "75691.43436 01033.723402 00149.330011 6 90380000250000";
+
+        assert_eq!(linha_digitavel.formatted(), expected);
+        assert_eq!(format!("{linha_digitavel:#}"), expected);
+        assert_eq!(format!("{linha_digitavel}"), "75691434360103372340200149330011690380000250000");
+    }
+
     #[test]
     fn validate_convert_barcode_to_linha_digitavel() {
         let barcodes = [
@@ -633,4 +1070,18 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn to_svg_with_linha_digitavel() {
+        let cod_barras = CodBarras::new(b"10499898100000214032006561000100040099726390").unwrap();
+        let linha_digitavel = LinhaDigitavel::from(&cod_barras).formatted();
+
+        let svg = cod_barras.to_svg(itf::Config::default(), false).unwrap();
+        assert!(svg.contains(r#"width="112.87mm" height="13mm""#));
+        assert!(!svg.contains(&linha_digitavel));
+
+        let svg = cod_barras.to_svg(itf::Config::default(), true).unwrap();
+        assert!(svg.contains(r#"width="112.87mm" height="19mm""#));
+        assert!(svg.contains(&format!(">{linha_digitavel}</text>")));
+    }
 }
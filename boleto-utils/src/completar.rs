@@ -0,0 +1,250 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::diagnostico::Campo;
+use crate::{arrecadacao, cobranca, Arrecadacao, Boleto, BoletoError, Cobranca};
+
+/// Dígito verificador alterado por `Boleto::complete`. A posição se refere à
+/// entrada original.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Alteracao {
+    pub campo: Campo,
+    pub posicao: usize,
+    pub anterior: char,
+    pub corrigido: char,
+}
+
+/// Código de barras e linha digitável com todos os dígitos verificadores
+/// calculados
+#[derive(Debug)]
+pub enum Completado {
+    Cobranca {
+        cod_barras: cobranca::CodBarras,
+        linha_digitavel: cobranca::LinhaDigitavel,
+        alteracoes: Vec<Alteracao>,
+    },
+    Arrecadacao {
+        cod_barras: arrecadacao::CodBarras,
+        linha_digitavel: arrecadacao::LinhaDigitavel,
+        alteracoes: Vec<Alteracao>,
+    },
+}
+
+impl Completado {
+    pub fn cod_barras(&self) -> &str {
+        match self {
+            Self::Cobranca { cod_barras, .. } => cod_barras.as_str(),
+            Self::Arrecadacao { cod_barras, .. } => cod_barras.as_str(),
+        }
+    }
+
+    pub fn linha_digitavel(&self) -> &str {
+        match self {
+            Self::Cobranca { linha_digitavel, .. } => linha_digitavel.as_str(),
+            Self::Arrecadacao { linha_digitavel, .. } => linha_digitavel.as_str(),
+        }
+    }
+
+    pub fn alteracoes(&self) -> &[Alteracao] {
+        match self {
+            Self::Cobranca { alteracoes, .. } | Self::Arrecadacao { alteracoes, .. } => alteracoes,
+        }
+    }
+
+    pub fn dv_geral(&self) -> u8 {
+        match self {
+            Self::Cobranca { cod_barras, .. } => cod_barras[4] - b'0',
+            Self::Arrecadacao { cod_barras, .. } => cod_barras[3] - b'0',
+        }
+    }
+
+    /// Dígitos verificadores de cada campo da linha digitável
+    pub fn dvs_campos(&self) -> Vec<u8> {
+        match self {
+            Self::Cobranca { cod_barras, .. } => {
+                let (dv1, dv2, dv3) = cod_barras.calculate_dv_campos();
+                [dv1, dv2, dv3].map(|dv| dv - b'0').to_vec()
+            },
+            Self::Arrecadacao { cod_barras, .. } => {
+                let (dv1, dv2, dv3, dv4) = cod_barras.calculate_dv_campos();
+                [dv1, dv2, dv3, dv4].map(|dv| dv - b'0').to_vec()
+            },
+        }
+    }
+}
+
+/// Compara a entrada com o resultado no mesmo formato, associando cada
+/// posição alterada ao campo correspondente
+fn alteracoes(input: &[u8], completo: &[u8], campos: &[(usize, Campo)]) -> Vec<Alteracao> {
+    campos.iter()
+        .filter(|(posicao, _)| input[*posicao] != completo[*posicao])
+        .map(|(posicao, campo)| Alteracao {
+            campo: *campo,
+            posicao: *posicao,
+            anterior: input[*posicao] as char,
+            corrigido: completo[*posicao] as char,
+        })
+        .collect()
+}
+
+impl Boleto {
+    /// Calcula o dígito verificador geral e os dígitos verificadores dos
+    /// campos de um código de barras ou linha digitável, validando apenas o
+    /// mínimo necessário para o cálculo. As posições dos dígitos
+    /// verificadores podem estar erradas ou zeradas.
+    pub fn complete(input: &[u8]) -> Result<Completado, BoletoError> {
+        match (input.first(), input.len()) {
+            (None, _) => Err(BoletoError::InvalidLength),
+            (Some(b'8'), Arrecadacao::COD_BARRAS_LENGTH) => {
+                let mut cod_barras = arrecadacao::CodBarras::new(input)?;
+                cod_barras.update_dv();
+
+                Ok(Completado::Arrecadacao {
+                    alteracoes: alteracoes(input, cod_barras.as_bytes(), &[(3, Campo::DvGeral)]),
+                    linha_digitavel: arrecadacao::LinhaDigitavel::try_from(&cod_barras)?,
+                    cod_barras,
+                })
+            },
+            (Some(b'8'), Arrecadacao::LINHA_DIGITAVEL_LENGTH) => {
+                let mut cod_barras = arrecadacao::CodBarras::from(&arrecadacao::LinhaDigitavel::new(input)?);
+                cod_barras.tipo_valor()?;
+                cod_barras.update_dv();
+
+                let linha_digitavel = arrecadacao::LinhaDigitavel::try_from(&cod_barras)?;
+
+                Ok(Completado::Arrecadacao {
+                    alteracoes: alteracoes(input, &linha_digitavel[..], &[
+                        (3, Campo::DvGeral),
+                        (11, Campo::Campo1),
+                        (23, Campo::Campo2),
+                        (35, Campo::Campo3),
+                        (47, Campo::Campo4),
+                    ]),
+                    cod_barras,
+                    linha_digitavel,
+                })
+            },
+            (Some(b'8'), _) => Err(BoletoError::InvalidLength),
+            (_, Cobranca::COD_BARRAS_LENGTH) => {
+                let mut cod_barras = cobranca::CodBarras::new(input)?;
+                cod_barras.update_dv();
+
+                Ok(Completado::Cobranca {
+                    alteracoes: alteracoes(input, cod_barras.as_bytes(), &[(4, Campo::DvGeral)]),
+                    linha_digitavel: cobranca::LinhaDigitavel::from(&cod_barras),
+                    cod_barras,
+                })
+            },
+            (_, Cobranca::LINHA_DIGITAVEL_LENGTH) => {
+                let mut cod_barras = cobranca::CodBarras::from(&cobranca::LinhaDigitavel::new(input)?);
+                cod_barras.update_dv();
+
+                let linha_digitavel = cobranca::LinhaDigitavel::from(&cod_barras);
+
+                Ok(Completado::Cobranca {
+                    alteracoes: alteracoes(input, &linha_digitavel[..], &[
+                        (9, Campo::Campo1),
+                        (20, Campo::Campo2),
+                        (31, Campo::Campo3),
+                        (32, Campo::DvGeral),
+                    ]),
+                    cod_barras,
+                    linha_digitavel,
+                })
+            },
+            _ => Err(BoletoError::InvalidLength),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zerar(input: &str, posicoes: &[usize]) -> Vec<u8> {
+        let mut input = input.as_bytes().to_vec();
+        for posicao in posicoes {
+            input[*posicao] = b'0';
+        }
+        input
+    }
+
+    #[test]
+    fn complete_cobranca() {
+        let linha = "75691434360103372340200149330011690380000250000";
+        let completado = Boleto::complete(&zerar(linha, &[9, 20, 31, 32])).unwrap();
+
+        assert_eq!(completado.linha_digitavel(), linha);
+        assert_eq!(completado.cod_barras(), "75696903800002500001434301033723400014933001");
+        assert_eq!(completado.dv_geral(), 6);
+        assert_eq!(completado.dvs_campos(), vec![6, 2, 1]);
+        assert_eq!(
+            completado.alteracoes(),
+            &[
+                Alteracao { campo: Campo::Campo1, posicao: 9, anterior: '0', corrigido: '6' },
+                Alteracao { campo: Campo::Campo2, posicao: 20, anterior: '0', corrigido: '2' },
+                Alteracao { campo: Campo::Campo3, posicao: 31, anterior: '0', corrigido: '1' },
+                Alteracao { campo: Campo::DvGeral, posicao: 32, anterior: '0', corrigido: '6' },
+            ],
+        );
+
+        let completado = Boleto::complete(&zerar("75696903800002500001434301033723400014933001", &[4])).unwrap();
+
+        assert_eq!(completado.linha_digitavel(), linha);
+        assert_eq!(
+            completado.alteracoes(),
+            &[Alteracao { campo: Campo::DvGeral, posicao: 4, anterior: '0', corrigido: '6' }],
+        );
+
+        // Código de barras já com o DV correto
+        let completado = Boleto::complete(b"12345678901234567890123456789012345678901234").unwrap();
+
+        assert_eq!(completado.dv_geral(), 5);
+        assert_eq!(completado.dvs_campos(), vec![0, 3, 3]);
+        assert!(completado.alteracoes().is_empty());
+    }
+
+    #[test]
+    fn complete_arrecadacao() {
+        let linha = "836555555553555566667773777777777775777777777775";
+        let completado = Boleto::complete(&zerar(linha, &[3, 11, 23, 35, 47])).unwrap();
+
+        assert_eq!(completado.linha_digitavel(), linha);
+        assert_eq!(completado.cod_barras(), "83655555555555566667777777777777777777777777");
+        assert_eq!(completado.dv_geral(), 5);
+        assert_eq!(completado.dvs_campos(), vec![3, 3, 5, 5]);
+        assert_eq!(
+            completado.alteracoes().iter().map(|a| (a.campo, a.posicao)).collect::<Vec<_>>(),
+            vec![
+                (Campo::DvGeral, 3),
+                (Campo::Campo1, 11),
+                (Campo::Campo2, 23),
+                (Campo::Campo3, 35),
+                (Campo::Campo4, 47),
+            ],
+        );
+
+        let completado = Boleto::complete(&zerar("83655555555555566667777777777777777777777777", &[3])).unwrap();
+
+        assert_eq!(completado.linha_digitavel(), linha);
+        assert_eq!(
+            completado.alteracoes(),
+            &[Alteracao { campo: Campo::DvGeral, posicao: 3, anterior: '0', corrigido: '5' }],
+        );
+    }
+
+    #[test]
+    fn complete_invalid_input() {
+        assert!(matches!(Boleto::complete(b""), Err(BoletoError::InvalidLength)));
+        assert!(matches!(Boleto::complete(b"8080"), Err(BoletoError::InvalidLength)));
+        assert!(matches!(
+            Boleto::complete(b"805000000000000000000000000000000000000000001111"),
+            Err(BoletoError::InvalidTipoValor),
+        ));
+        assert!(matches!(
+            Boleto::complete(b"1234567890123456789012345678901234567890123a"),
+            Err(BoletoError::NumbersOnly),
+        ));
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+
+use crate::arrecadacao::{Convenio, Segmento};
+
+/// Lista de convênios da Febraban (`segmento,numero,name`)
+const LISTA_CONVENIOS_CSV: &str = include_str!("../data/lista-convenios.csv");
+
+/// Listas de concessionárias por segmento (`nome,codigo`)
+const CONCESSIONARIAS_CSV: [(u8, &str); 8] = [
+    (b'1', include_str!("../data/concessionarias-1-prefeituras.csv")),
+    (b'2', include_str!("../data/concessionarias-2-saneamento.csv")),
+    (b'2', include_str!("../data/concessionarias-saneamento.csv")),
+    (b'3', include_str!("../data/concessionarias-3-energia-e-gas.csv")),
+    (b'4', include_str!("../data/concessionarias-4-telefonia.csv")),
+    (b'5', include_str!("../data/concessionarias-5-orgaos-governamentais.csv")),
+    (b'6', include_str!("../data/concessionarias-6-carnes.csv")),
+    (b'7', include_str!("../data/concessionarias-7-multas.csv")),
+];
+
+lazy_static! {
+    /// Nomes das empresas/órgãos indexados por (segmento, código do convênio).
+    ///
+    /// A lista da Febraban é carregada primeiro e as listas de concessionárias
+    /// por segmento a sobrescrevem, ou seja, quando as duas fontes divergem
+    /// prevalece o nome das listas de concessionárias. Entre as listas de
+    /// concessionárias, prevalece a que aparece por último em `CONCESSIONARIAS_CSV`.
+    static ref CONVENIOS: HashMap<(u8, u32), String> = {
+        let mut convenios = HashMap::new();
+
+        let mut reader = csv::Reader::from_reader(LISTA_CONVENIOS_CSV.as_bytes());
+        for record in reader.records() {
+            let record = record.expect("data/lista-convenios.csv deve ser um CSV válido");
+
+            // Linhas com segmento ou número inválidos são ignoradas
+            let (Some(segmento), Some(numero)) = (parse_segmento(&record[0]), parse_codigo(&record[1])) else {
+                continue;
+            };
+
+            convenios.insert((segmento, numero), record[2].to_owned());
+        }
+
+        for (segmento, data) in CONCESSIONARIAS_CSV {
+            let mut reader = csv::Reader::from_reader(data.as_bytes());
+            for record in reader.records() {
+                let record = record.expect("data/concessionarias-*.csv deve ser um CSV válido");
+
+                let Some(codigo) = parse_codigo(&record[1]) else {
+                    continue;
+                };
+
+                convenios.insert((segmento, codigo), record[0].to_owned());
+            }
+        }
+
+        convenios
+    };
+}
+
+fn parse_segmento(value: &str) -> Option<u8> {
+    match value.as_bytes() {
+        [segmento] => Segmento::try_from(*segmento).ok().map(|s| (&s).into()),
+        _ => None,
+    }
+}
+
+fn parse_codigo(value: &str) -> Option<u32> {
+    if value.is_empty() || value.len() > 8 || !value.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    value.parse().ok()
+}
+
+/// Busca o nome da empresa/órgão de um convênio dentro do seu segmento
+pub fn get(segmento: &Segmento, convenio: &Convenio) -> Option<&'static str> {
+    let codigo = match convenio {
+        Convenio::Outros(numero) => u32::from(*numero),
+        Convenio::Carne(cadastro) => parse_codigo(std::str::from_utf8(cadastro).ok()?)?,
+    };
+
+    CONVENIOS
+        .get(&(segmento.into(), codigo))
+        .map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_convenio_correctly() {
+        let cases = [
+            (Segmento::Prefeituras, Convenio::Outros(1), "ABADIA DOURADOS/MG"),
+            (Segmento::Saneamento, Convenio::Outros(1346), "BRK AMBIENTAL ARAGUAIA SANEAMENTO SA"),
+            (Segmento::Saneamento, Convenio::Outros(1263), "BRK Ambiental Santa Gertrudes"),
+            (Segmento::Saneamento, Convenio::Outros(1699), "Águas do Rio"),
+            (Segmento::EnergiaEletricaEGas, Convenio::Outros(1), "Caiuá Serviço de Eletricidade"),
+            (Segmento::MultasTransito, Convenio::Outros(3980), "CET (Santos/SP)"),
+            (Segmento::Carnes, Convenio::Carne(*b"44602720"), "EMDEC (Campinas/SP)"),
+            (Segmento::ExclusivoDoBanco, Convenio::Outros(82), "SEFAZ/CE"),
+        ];
+
+        for (segmento, convenio, expected) in cases {
+            assert_eq!(get(&segmento, &convenio), Some(expected));
+        }
+
+        assert_eq!(get(&Segmento::Carnes, &Convenio::Carne(*b"99999999")), None);
+        assert_eq!(get(&Segmento::Telecomunicacoes, &Convenio::Outros(9999)), None);
+    }
+
+    #[test]
+    fn concessionarias_take_precedence() {
+        // "P.M.S.P/SP" na lista da Febraban
+        assert_eq!(get(&Segmento::Prefeituras, &Convenio::Outros(0)), Some("São Paulo/SP"));
+        // "AGESPISA S/A" na lista da Febraban
+        assert_eq!(
+            get(&Segmento::Saneamento, &Convenio::Outros(1)),
+            Some("Agespisa Água e Esgoto do Piauí"),
+        );
+    }
+
+    #[test]
+    fn lookup_is_segment_aware() {
+        assert_ne!(
+            get(&Segmento::Saneamento, &Convenio::Outros(1)),
+            get(&Segmento::EnergiaEletricaEGas, &Convenio::Outros(1)),
+        );
+    }
+}
@@ -0,0 +1,215 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::arrecadacao::Arrecadacao;
+use crate::cobranca::Cobranca;
+use crate::{Boleto, BoletoError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tipo"))]
+pub enum TipoCorrecao {
+    /// Um único dígito digitado errado
+    #[cfg_attr(feature = "serde", serde(rename = "substituicao"))]
+    Substituicao { posicao: usize, digitado: char, correto: char },
+    /// Dois dígitos vizinhos digitados em ordem trocada
+    #[cfg_attr(feature = "serde", serde(rename = "transposicao"))]
+    Transposicao { posicao: usize },
+}
+
+/// Sugestão de correção para um código de barras ou linha digitável inválido.
+/// Posições começam em 0 e se referem à entrada original.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Correcao {
+    pub corrigido: String,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub tipo: TipoCorrecao,
+}
+
+impl Correcao {
+    /// Quanto menor, mais provável o erro de digitação: dígitos vizinhos no
+    /// teclado numérico, depois transposições e por fim qualquer outra
+    /// substituição
+    fn peso(&self) -> u8 {
+        match self.tipo {
+            TipoCorrecao::Substituicao { digitado, correto, .. } if vizinhos(digitado, correto) => 0,
+            TipoCorrecao::Transposicao { .. } => 1,
+            TipoCorrecao::Substituicao { .. } => 2,
+        }
+    }
+}
+
+/// Indica se as teclas são vizinhas (na horizontal ou vertical) no teclado
+/// numérico:
+///
+/// ```text
+/// 7 8 9
+/// 4 5 6
+/// 1 2 3
+/// 0
+/// ```
+fn vizinhos(a: char, b: char) -> bool {
+    let posicao = |c: char| match c {
+        '0' => (3, 0),
+        c => {
+            let n = c as i32 - '1' as i32;
+            (2 - n / 3, n % 3)
+        },
+    };
+
+    let ((la, ca), (lb, cb)) = (posicao(a), posicao(b));
+
+    (la - lb).abs() + (ca - cb).abs() == 1
+}
+
+impl Boleto {
+    /// Procura substituições de um dígito e transposições de dígitos vizinhos
+    /// que tornam a entrada válida, usando os dígitos verificadores de cada
+    /// campo e o dígito verificador geral. As sugestões são ordenadas da mais
+    /// para a menos provável.
+    ///
+    /// Entradas já válidas não têm sugestões.
+    pub fn suggest_corrections(input: &[u8]) -> Result<Vec<Correcao>, BoletoError> {
+        if ![
+            Cobranca::COD_BARRAS_LENGTH,
+            Cobranca::LINHA_DIGITAVEL_LENGTH,
+            Arrecadacao::LINHA_DIGITAVEL_LENGTH,
+        ].contains(&input.len()) {
+            return Err(BoletoError::InvalidLength);
+        }
+
+        if !input.iter().all(|c| c.is_ascii_digit()) {
+            return Err(BoletoError::NumbersOnly);
+        }
+
+        if Boleto::new(input).is_ok() {
+            return Ok(Vec::new());
+        }
+
+        let mut candidato = input.to_vec();
+        let mut correcoes = Vec::new();
+
+        for posicao in 0..input.len() {
+            for correto in b'0'..=b'9' {
+                if correto == input[posicao] {
+                    continue;
+                }
+
+                candidato[posicao] = correto;
+
+                if Boleto::new(&candidato).is_ok() {
+                    correcoes.push(Correcao {
+                        corrigido: String::from_utf8(candidato.clone()).unwrap(),
+                        tipo: TipoCorrecao::Substituicao {
+                            posicao,
+                            digitado: input[posicao] as char,
+                            correto: correto as char,
+                        },
+                    });
+                }
+            }
+
+            candidato[posicao] = input[posicao];
+        }
+
+        for posicao in 0..input.len() - 1 {
+            if input[posicao] == input[posicao + 1] {
+                continue;
+            }
+
+            candidato.swap(posicao, posicao + 1);
+
+            if Boleto::new(&candidato).is_ok() {
+                correcoes.push(Correcao {
+                    corrigido: String::from_utf8(candidato.clone()).unwrap(),
+                    tipo: TipoCorrecao::Transposicao { posicao },
+                });
+            }
+
+            candidato.swap(posicao, posicao + 1);
+        }
+
+        correcoes.sort_by_key(Correcao::peso);
+
+        Ok(correcoes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINHA_DIGITAVEL: &str = "75691434360103372340200149330011690380000250000";
+
+    fn typo(posicao: usize, digito: u8) -> Vec<u8> {
+        let mut input = LINHA_DIGITAVEL.as_bytes().to_vec();
+        input[posicao] = digito;
+        input
+    }
+
+    #[test]
+    fn check_vizinhos_correctly() {
+        assert!(vizinhos('5', '8'));
+        assert!(vizinhos('1', '2'));
+        assert!(vizinhos('0', '1'));
+        assert!(!vizinhos('0', '2'));
+        assert!(!vizinhos('3', '4'));
+        assert!(!vizinhos('1', '5'));
+    }
+
+    #[test]
+    fn suggest_substituicao() {
+        // '3' digitado como '6' (vizinho no teclado)
+        let correcoes = Boleto::suggest_corrections(&typo(14, b'6')).unwrap();
+        let esperada = Correcao {
+            corrigido: LINHA_DIGITAVEL.to_owned(),
+            tipo: TipoCorrecao::Substituicao { posicao: 14, digitado: '6', correto: '3' },
+        };
+
+        // Outras correções podem ser igualmente prováveis, mas todas as mais
+        // prováveis vêm antes das demais
+        assert!(correcoes.iter().take_while(|c| c.peso() == 0).any(|c| *c == esperada));
+        assert!(correcoes.len() < 10);
+
+        // Erro no próprio dígito verificador do campo
+        let correcoes = Boleto::suggest_corrections(&typo(20, b'9')).unwrap();
+
+        assert!(correcoes.iter().any(|c| c.corrigido == LINHA_DIGITAVEL));
+    }
+
+    #[test]
+    fn suggest_transposicao() {
+        let mut input = LINHA_DIGITAVEL.as_bytes().to_vec();
+        input.swap(22, 23);
+
+        let correcoes = Boleto::suggest_corrections(&input).unwrap();
+        let correcao = correcoes.iter().find(|c| c.corrigido == LINHA_DIGITAVEL).unwrap();
+
+        assert_eq!(correcao.tipo, TipoCorrecao::Transposicao { posicao: 22 });
+        assert!(correcoes.windows(2).all(|w| w[0].peso() <= w[1].peso()));
+    }
+
+    #[test]
+    fn suggest_arrecadacao() {
+        let linha_digitavel = b"836555555553555566667773777777777775777777777775";
+        assert!(Boleto::new(linha_digitavel).is_ok());
+
+        let mut input = linha_digitavel.to_vec();
+        input[5] = b'4';
+
+        let correcoes = Boleto::suggest_corrections(&input).unwrap();
+
+        assert!(correcoes.iter().any(|c| c.corrigido.as_bytes() == linha_digitavel));
+    }
+
+    #[test]
+    fn no_suggestions() {
+        assert!(Boleto::suggest_corrections(LINHA_DIGITAVEL.as_bytes()).unwrap().is_empty());
+        assert!(matches!(Boleto::suggest_corrections(b"123"), Err(BoletoError::InvalidLength)));
+        assert!(matches!(
+            Boleto::suggest_corrections(&typo(14, b'a')),
+            Err(BoletoError::NumbersOnly),
+        ));
+    }
+}
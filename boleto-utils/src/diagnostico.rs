@@ -0,0 +1,229 @@
+use std::fmt;
+use std::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::BoletoError;
+
+/// Parte do código de barras ou da linha digitável a que um diagnóstico se
+/// refere
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Campo {
+    Tamanho,
+    /// Primeiro dígito, que distingue cobrança ('8') de arrecadação
+    TipoBoleto,
+    Caractere,
+    Campo1,
+    Campo2,
+    Campo3,
+    Campo4,
+    DvGeral,
+    CodigoMoeda,
+    FatorVencimento,
+    Segmento,
+    TipoValor,
+    CampoLivre,
+}
+
+impl Campo {
+    /// Campo da linha digitável pelo índice (começando em 0)
+    pub(crate) fn linha_digitavel(indice: usize) -> Self {
+        [Self::Campo1, Self::Campo2, Self::Campo3, Self::Campo4][indice]
+    }
+}
+
+impl fmt::Display for Campo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Tamanho => "tamanho",
+            Self::TipoBoleto => "tipo de boleto",
+            Self::Caractere => "caractere",
+            Self::Campo1 => "campo 1",
+            Self::Campo2 => "campo 2",
+            Self::Campo3 => "campo 3",
+            Self::Campo4 => "campo 4",
+            Self::DvGeral => "DV geral",
+            Self::CodigoMoeda => "código moeda",
+            Self::FatorVencimento => "fator de vencimento",
+            Self::Segmento => "segmento",
+            Self::TipoValor => "tipo de valor",
+            Self::CampoLivre => "campo livre",
+        })
+    }
+}
+
+/// Problema encontrado na validação de um código de barras ou linha
+/// digitável
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Diagnostic {
+    pub campo: Campo,
+    /// Posições (em bytes) na entrada original. Para os dígitos verificadores
+    /// dos campos da linha digitável, é o campo inteiro.
+    pub posicao: Range<usize>,
+    pub esperado: Option<String>,
+    pub encontrado: String,
+    #[cfg_attr(feature = "serde", serde(rename = "mensagem", serialize_with = "serialize_erro"))]
+    pub erro: BoletoError,
+}
+
+#[cfg(feature = "serde")]
+fn serialize_erro<S>(erro: &BoletoError, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer {
+    serializer.collect_str(erro)
+}
+
+impl Diagnostic {
+    /// Diagnóstico em que o valor encontrado é o trecho da entrada na posição
+    pub(crate) fn new(campo: Campo, input: &[u8], posicao: Range<usize>, erro: BoletoError) -> Self {
+        Self {
+            campo,
+            encontrado: String::from_utf8_lossy(&input[posicao.clone()]).into_owned(),
+            posicao,
+            esperado: None,
+            erro,
+        }
+    }
+
+    /// Diagnóstico de dígito verificador, com os dígitos esperado e encontrado
+    pub(crate) fn dv(campo: Campo, posicao: Range<usize>, esperado: u8, encontrado: u8, erro: BoletoError) -> Self {
+        Self {
+            campo,
+            posicao,
+            esperado: Some(char::from(esperado).to_string()),
+            encontrado: char::from(encontrado).to_string(),
+            erro,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (posições {} a {}): {}", self.campo, self.posicao.start, self.posicao.end, self.erro)?;
+
+        if let Some(esperado) = &self.esperado {
+            write!(f, ", esperado '{esperado}'")?;
+        }
+
+        write!(f, ", encontrado '{}'", self.encontrado)
+    }
+}
+
+/// Valida tamanho, primeiro dígito e caracteres da entrada, nessa ordem.
+/// Caracteres inválidos são todos reportados.
+pub(crate) fn validar_estrutura(input: &[u8], tamanhos: &[usize], arrecadacao: bool) -> Result<(), Vec<Diagnostic>> {
+    if !tamanhos.contains(&input.len()) {
+        return Err(vec![Diagnostic {
+            campo: Campo::Tamanho,
+            posicao: 0..input.len(),
+            esperado: Some(tamanhos.iter().map(|t| t.to_string()).collect::<Vec<_>>().join(" ou ")),
+            encontrado: input.len().to_string(),
+            erro: BoletoError::InvalidLength,
+        }]);
+    }
+
+    if (input[0] == b'8') != arrecadacao {
+        return Err(vec![Diagnostic::new(Campo::TipoBoleto, input, 0..1, BoletoError::InvalidArrecadacaoBarcode)]);
+    }
+
+    let caracteres: Vec<Diagnostic> = input.iter()
+        .enumerate()
+        .filter(|(_, c)| !c.is_ascii_digit())
+        .map(|(i, _)| Diagnostic::new(Campo::Caractere, input, i..i + 1, BoletoError::NumbersOnly))
+        .collect();
+
+    if !caracteres.is_empty() {
+        return Err(caracteres);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Boleto;
+
+    fn alterar(input: &str, alteracoes: &[(usize, u8)]) -> Vec<u8> {
+        let mut input = input.as_bytes().to_vec();
+        for (posicao, digito) in alteracoes {
+            input[*posicao] = *digito;
+        }
+        input
+    }
+
+    #[test]
+    fn diagnose_cobranca() {
+        // Código moeda, DV do campo 3 e DV geral inválidos
+        let input = alterar(
+            "75691434360103372340200149330011690380000250000",
+            &[(3, b'5'), (31, b'2'), (32, b'7')],
+        );
+        let diagnostics = Boleto::diagnose(&input).unwrap_err();
+
+        assert_eq!(
+            diagnostics.iter().map(|d| (d.campo, d.posicao.clone())).collect::<Vec<_>>(),
+            vec![
+                (Campo::CodigoMoeda, 3..4),
+                (Campo::DvGeral, 32..33),
+                (Campo::Campo1, 0..10),
+                (Campo::Campo3, 21..32),
+            ],
+        );
+        assert_eq!(diagnostics[0].erro, BoletoError::InvalidCodigoMoeda);
+        assert_eq!(diagnostics[3].esperado.as_deref(), Some("1"));
+        assert_eq!(diagnostics[3].encontrado, "2");
+        assert_eq!(
+            diagnostics[3].to_string(),
+            "campo 3 (posições 21 a 32): dígito verificador de campos inválido, esperado '1', encontrado '2'",
+        );
+
+        // Fator de vencimento no código de barras
+        let diagnostics = Boleto::diagnose(b"11196099955555555556666666666666666666666666").unwrap_err();
+
+        assert_eq!(diagnostics[0].campo, Campo::FatorVencimento);
+        assert_eq!(diagnostics[0].posicao, 5..9);
+        assert_eq!(diagnostics[0].encontrado, "0999");
+    }
+
+    #[test]
+    fn diagnose_arrecadacao() {
+        // DVs dos campos 2 e 4
+        let input = alterar("836555555553555566667773777777777775777777777775", &[(23, b'4'), (47, b'0')]);
+        let diagnostics = Boleto::diagnose(&input).unwrap_err();
+
+        assert_eq!(
+            diagnostics.iter().map(|d| (d.campo, d.posicao.clone())).collect::<Vec<_>>(),
+            vec![(Campo::Campo2, 12..24), (Campo::Campo4, 36..48)],
+        );
+        assert_eq!(diagnostics[1].esperado.as_deref(), Some("5"));
+
+        // Segmento e tipo de valor inválidos impedem o cálculo dos DVs
+        let diagnostics = Boleto::diagnose(b"80055555555555566667777777777777777777777777").unwrap_err();
+
+        assert_eq!(
+            diagnostics.iter().map(|d| d.campo).collect::<Vec<_>>(),
+            vec![Campo::TipoValor, Campo::Segmento],
+        );
+    }
+
+    #[test]
+    fn diagnose_estrutura() {
+        let diagnostics = Boleto::diagnose(b"7569143436010337234020014933001169038000025000x").unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!((diagnostics[0].campo, diagnostics[0].posicao.clone()), (Campo::Caractere, 46..47));
+
+        let diagnostics = Boleto::diagnose(b"a569143436010337234020014933001169038000025000x").unwrap_err();
+        assert_eq!(diagnostics.len(), 2);
+
+        let diagnostics = Boleto::diagnose(b"123").unwrap_err();
+        assert_eq!(diagnostics[0].campo, Campo::Tamanho);
+        assert_eq!(diagnostics[0].esperado.as_deref(), Some("44 ou 47"));
+
+        assert!(Boleto::diagnose(b"75691434360103372340200149330011690380000250000").is_ok());
+    }
+}
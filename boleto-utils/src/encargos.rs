@@ -0,0 +1,386 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::calendario::Calendario;
+use crate::cobranca::Cobranca;
+use crate::fator_vencimento::{Data, DataCivil};
+use crate::valor::Valor;
+use crate::BoletoError;
+
+/// Percentual com duas casas decimais, armazenado em centésimos de ponto
+/// percentual (`2,5%` == 250)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Percentual(u32);
+
+impl Percentual {
+    pub const fn from_centesimos(centesimos: u32) -> Self {
+        Self(centesimos)
+    }
+
+    pub const fn centesimos(&self) -> u32 {
+        self.0
+    }
+
+    /// Aplica o percentual `vezes / divisor` vezes sobre o valor, arredondando
+    /// o resultado para o centavo mais próximo apenas no final. Retorna `None`
+    /// se o resultado não couber em `Valor`.
+    fn aplicar(&self, valor: Valor, vezes: u64, divisor: u64) -> Option<Valor> {
+        let numerador = valor.centavos() as u128 * self.0 as u128 * vezes as u128;
+        let denominador = 10_000 * divisor as u128;
+
+        u64::try_from((numerador + denominador / 2) / denominador)
+            .ok()
+            .map(Valor::from_centavos)
+    }
+}
+
+/// Formata o percentual: `2,50%`
+impl fmt::Display for Percentual {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{:02}%", self.0 / 100, self.0 % 100)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValorOuPercentual {
+    Valor(Valor),
+    /// Percentual sobre o valor do documento
+    Percentual(Percentual),
+}
+
+impl ValorOuPercentual {
+    fn sobre(&self, valor: Valor) -> Option<Valor> {
+        match self {
+            Self::Valor(v) => Some(*v),
+            Self::Percentual(p) => p.aplicar(valor, 1, 1),
+        }
+    }
+}
+
+/// Juros simples sobre o valor do documento, contados por dia corrido desde a
+/// data de vencimento
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Juros {
+    /// Valor fixo por dia de atraso
+    ValorDiario(Valor),
+    /// Percentual por dia de atraso
+    PercentualDiario(Percentual),
+    /// Percentual ao mês, proporcional aos dias de atraso (mês comercial de
+    /// 30 dias)
+    PercentualMensal(Percentual),
+}
+
+impl Juros {
+    fn sobre(&self, valor: Valor, dias: u64) -> Option<Valor> {
+        match self {
+            Self::ValorDiario(v) => v.centavos().checked_mul(dias).map(Valor::from_centavos),
+            Self::PercentualDiario(p) => p.aplicar(valor, dias, 1),
+            Self::PercentualMensal(p) => p.aplicar(valor, dias, 30),
+        }
+    }
+}
+
+/// Desconto concedido para pagamentos até a data informada (inclusive)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Desconto {
+    pub ate: Data,
+    pub desconto: ValorOuPercentual,
+}
+
+/// Regras de multa, juros e desconto do boleto.
+///
+/// Assim como o vencimento, datas limite de desconto e o fim da carência que
+/// caem em dia não útil são prorrogados para o dia útil seguinte de acordo com
+/// o calendário.
+#[derive(Debug, Clone, Default)]
+pub struct RegrasCobranca {
+    pub multa: Option<ValorOuPercentual>,
+    pub juros: Option<Juros>,
+    /// Dias corridos após o vencimento em que multa e juros ainda não incidem
+    pub carencia: u32,
+    pub descontos: Vec<Desconto>,
+    pub calendario: Calendario,
+}
+
+/// Demonstrativo do valor a pagar em uma data
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Demonstrativo {
+    pub data_pagamento: Data,
+    pub valor_documento: Valor,
+    pub desconto: Valor,
+    pub multa: Valor,
+    pub juros: Valor,
+    /// Dias corridos desde o vencimento, apenas para pagamentos após a data
+    /// limite
+    pub dias_atraso: u32,
+    pub total: Valor,
+}
+
+impl RegrasCobranca {
+    /// Calcula o valor a pagar na data informada.
+    ///
+    /// Multa e juros só incidem após o vencimento somado à carência e
+    /// prorrogado para o dia útil seguinte, mas os juros são contados desde a
+    /// data de vencimento. Quando mais de uma faixa de desconto é válida, vale
+    /// a de data limite mais próxima. Boletos sem valor ou encargos que não
+    /// cabem em `Valor` resultam em erro.
+    pub fn calcular(&self, cobranca: &Cobranca, data_pagamento: Data) -> Result<Demonstrativo, BoletoError> {
+        let valor_documento = cobranca.valor.ok_or(BoletoError::InvalidValor)?;
+
+        let dias_atraso = match cobranca.data_vencimento {
+            Some(vencimento) if self.is_em_atraso(vencimento, data_pagamento) => {
+                data_pagamento.dias_desde(vencimento) as u32
+            },
+            _ => 0,
+        };
+
+        let (multa, juros) = if dias_atraso > 0 {
+            (
+                self.multa.map_or(Some(Valor::ZERO), |m| m.sobre(valor_documento)),
+                self.juros.map_or(Some(Valor::ZERO), |j| j.sobre(valor_documento, dias_atraso.into())),
+            )
+        } else {
+            (Some(Valor::ZERO), Some(Valor::ZERO))
+        };
+
+        let multa = multa.ok_or(BoletoError::InvalidValor)?;
+        let juros = juros.ok_or(BoletoError::InvalidValor)?;
+
+        let desconto = self.descontos
+            .iter()
+            .map(|d| (self.calendario.proximo_dia_util(d.ate), d.desconto))
+            .filter(|(ate, _)| data_pagamento <= *ate)
+            .min_by_key(|(ate, _)| *ate)
+            // Descontos maiores que o documento são limitados ao valor dele
+            .map(|(_, desconto)| desconto.sobre(valor_documento).map_or(valor_documento, |d| d.min(valor_documento)))
+            .unwrap_or_default();
+
+        let total = (valor_documento.centavos() - desconto.centavos())
+            .checked_add(multa.centavos())
+            .and_then(|total| total.checked_add(juros.centavos()))
+            .ok_or(BoletoError::InvalidValor)?;
+
+        Ok(Demonstrativo {
+            data_pagamento,
+            valor_documento,
+            desconto,
+            multa,
+            juros,
+            dias_atraso,
+            total: Valor::from_centavos(total),
+        })
+    }
+
+    /// Indica se o pagamento é posterior ao fim da carência, prorrogado para o
+    /// dia útil seguinte
+    fn is_em_atraso(&self, vencimento: Data, data_pagamento: Data) -> bool {
+        vencimento
+            .somar_dias(self.carencia.into())
+            .is_some_and(|fim| data_pagamento > self.calendario.proximo_dia_util(fim))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::CobrancaBuilder;
+    use crate::cobranca::{CodBanco, CodigoMoeda};
+
+    fn date(y: i32, m: u32, d: u32) -> Data {
+        Data::new(y, m, d).unwrap()
+    }
+
+    /// R$ 1.000,00 com vencimento no sábado de Carnaval (pagável até 05/03/2025)
+    fn cobranca() -> Cobranca {
+        CobrancaBuilder::new()
+            .cod_banco(CodBanco(1))
+            .cod_moeda(CodigoMoeda::Real)
+            .data_vencimento(date(2025, 3, 1))
+            .valor(Valor::from_centavos(100_000))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn display_percentual_correctly() {
+        assert_eq!(Percentual::from_centesimos(250).to_string(), "2,50%");
+        assert_eq!(Percentual::from_centesimos(3).to_string(), "0,03%");
+    }
+
+    #[test]
+    fn calculate_multa_e_juros_correctly() {
+        let cobranca = cobranca();
+        let regras = RegrasCobranca {
+            multa: Some(ValorOuPercentual::Percentual(Percentual::from_centesimos(200))),
+            juros: Some(Juros::PercentualMensal(Percentual::from_centesimos(100))),
+            ..Default::default()
+        };
+
+        let demonstrativo = regras.calcular(&cobranca, date(2025, 3, 5)).unwrap();
+        assert_eq!(demonstrativo.dias_atraso, 0);
+        assert_eq!(demonstrativo.total, Valor::from_centavos(100_000));
+
+        let demonstrativo = regras.calcular(&cobranca, date(2025, 3, 6)).unwrap();
+        assert_eq!(
+            demonstrativo,
+            Demonstrativo {
+                data_pagamento: date(2025, 3, 6),
+                valor_documento: Valor::from_centavos(100_000),
+                desconto: Valor::ZERO,
+                multa: Valor::from_centavos(2_000),
+                // 1% ao mês por 5 dias: R$ 1,666...
+                juros: Valor::from_centavos(167),
+                dias_atraso: 5,
+                total: Valor::from_centavos(102_167),
+            },
+        );
+
+        let regras = RegrasCobranca {
+            multa: Some(ValorOuPercentual::Valor(Valor::from_centavos(1_000))),
+            juros: Some(Juros::PercentualDiario(Percentual::from_centesimos(3))),
+            ..Default::default()
+        };
+        let demonstrativo = regras.calcular(&cobranca, date(2025, 3, 11)).unwrap();
+        assert_eq!(demonstrativo.multa, Valor::from_centavos(1_000));
+        assert_eq!(demonstrativo.juros, Valor::from_centavos(300));
+        assert_eq!(demonstrativo.total, Valor::from_centavos(101_300));
+
+        let regras = RegrasCobranca {
+            juros: Some(Juros::ValorDiario(Valor::from_centavos(50))),
+            ..Default::default()
+        };
+        assert_eq!(regras.calcular(&cobranca, date(2025, 3, 6)).unwrap().juros, Valor::from_centavos(250));
+    }
+
+    #[test]
+    fn use_calendario_for_data_limite() {
+        let mut regras = RegrasCobranca {
+            multa: Some(ValorOuPercentual::Percentual(Percentual::from_centesimos(200))),
+            ..Default::default()
+        };
+        regras.calendario.adicionar_feriado(date(2025, 3, 5));
+
+        let demonstrativo = regras.calcular(&cobranca(), date(2025, 3, 6)).unwrap();
+        assert_eq!(demonstrativo.dias_atraso, 0);
+        assert_eq!(demonstrativo.multa, Valor::ZERO);
+    }
+
+    #[test]
+    fn calculate_desconto_correctly() {
+        let cobranca = cobranca();
+        let regras = RegrasCobranca {
+            descontos: vec![
+                Desconto {
+                    ate: date(2025, 2, 27),
+                    desconto: ValorOuPercentual::Percentual(Percentual::from_centesimos(200)),
+                },
+                Desconto {
+                    // Sábado, prorrogado para 24/02
+                    ate: date(2025, 2, 22),
+                    desconto: ValorOuPercentual::Percentual(Percentual::from_centesimos(500)),
+                },
+            ],
+            ..Default::default()
+        };
+
+        let cases = [
+            (date(2025, 2, 18), 5_000_u64),
+            (date(2025, 2, 24), 5_000),
+            (date(2025, 2, 25), 2_000),
+            (date(2025, 2, 27), 2_000),
+            (date(2025, 2, 28), 0),
+        ];
+
+        for (data_pagamento, expected) in cases {
+            let demonstrativo = regras.calcular(&cobranca, data_pagamento).unwrap();
+
+            assert_eq!(demonstrativo.desconto, Valor::from_centavos(expected), "{data_pagamento}");
+            assert_eq!(demonstrativo.total, Valor::from_centavos(100_000 - expected));
+        }
+
+        let regras = RegrasCobranca {
+            descontos: vec![Desconto {
+                ate: date(2025, 2, 27),
+                desconto: ValorOuPercentual::Valor(Valor::from_centavos(200_000)),
+            }],
+            ..Default::default()
+        };
+        assert_eq!(regras.calcular(&cobranca, date(2025, 2, 27)).unwrap().total, Valor::ZERO);
+    }
+
+    #[test]
+    fn respect_carencia() {
+        let regras = RegrasCobranca {
+            multa: Some(ValorOuPercentual::Percentual(Percentual::from_centesimos(200))),
+            juros: Some(Juros::ValorDiario(Valor::from_centavos(50))),
+            // 01/03 + 5 dias cai na quinta 06/03, que é dia útil
+            carencia: 5,
+            ..Default::default()
+        };
+
+        let demonstrativo = regras.calcular(&cobranca(), date(2025, 3, 6)).unwrap();
+        assert_eq!(demonstrativo.dias_atraso, 0);
+        assert_eq!(demonstrativo.total, Valor::from_centavos(100_000));
+
+        // Juros contados desde o vencimento
+        let demonstrativo = regras.calcular(&cobranca(), date(2025, 3, 7)).unwrap();
+        assert_eq!(demonstrativo.dias_atraso, 6);
+        assert_eq!(demonstrativo.juros, Valor::from_centavos(300));
+        assert_eq!(demonstrativo.multa, Valor::from_centavos(2_000));
+
+        // Fim da carência no sábado 08/03, prorrogado para 10/03
+        let regras = RegrasCobranca { carencia: 7, ..regras };
+        assert_eq!(regras.calcular(&cobranca(), date(2025, 3, 10)).unwrap().dias_atraso, 0);
+        assert_eq!(regras.calcular(&cobranca(), date(2025, 3, 11)).unwrap().dias_atraso, 10);
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        let cobranca = cobranca();
+        let invalidas = [
+            RegrasCobranca {
+                juros: Some(Juros::ValorDiario(Valor::from_centavos(u64::MAX / 2))),
+                ..Default::default()
+            },
+            RegrasCobranca {
+                multa: Some(ValorOuPercentual::Valor(Valor::from_centavos(u64::MAX))),
+                ..Default::default()
+            },
+            RegrasCobranca {
+                multa: Some(ValorOuPercentual::Valor(Valor::from_centavos(u64::MAX / 2))),
+                juros: Some(Juros::ValorDiario(Valor::from_centavos(u64::MAX / 4_000))),
+                ..Default::default()
+            },
+        ];
+
+        for regras in invalidas {
+            assert!(matches!(
+                regras.calcular(&cobranca, date(2034, 8, 22)),
+                Err(BoletoError::InvalidValor),
+            ));
+        }
+
+        let regras = RegrasCobranca {
+            juros: Some(Juros::PercentualDiario(Percentual::from_centesimos(u32::MAX))),
+            ..Default::default()
+        };
+        assert!(regras.calcular(&cobranca, date(2034, 8, 22)).is_ok());
+    }
+
+    #[test]
+    fn require_valor() {
+        let cobranca = CobrancaBuilder::new()
+            .cod_banco(CodBanco(1))
+            .cod_moeda(CodigoMoeda::Real)
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            RegrasCobranca::default().calcular(&cobranca, date(2025, 1, 1)),
+            Err(BoletoError::InvalidValor),
+        ));
+    }
+}
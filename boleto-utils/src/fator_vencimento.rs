@@ -0,0 +1,525 @@
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+use core::fmt;
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+use core::str::FromStr;
+
+#[cfg(all(feature = "serde", not(any(feature = "chrono", feature = "time"))))]
+use serde::de::{self, Visitor};
+#[cfg(all(feature = "serde", not(any(feature = "chrono", feature = "time"))))]
+use serde::{Deserialize, Serialize};
+
+use crate::BoletoError;
+
+// Fator para o dia 1º de Janeiro de 2010
+const FATOR_VENC_2010: u16 = 4469;
+
+/// Quantidade de dias entre duas datas base (o fator volta a 1000 após 9999)
+const CICLO: i64 = 9000;
+
+/// Data base usada até 2025 (1000 == 03/07/2000)
+const DATA_BASE: i64 = dias(1997, 10, 7);
+
+/// Data base usada de 2025 em diante (1000 == 22/02/2025)
+const PROXIMA_DATA_BASE: i64 = dias(2022, 5, 29);
+
+/// Primeiro dia em que a próxima data base é usada
+const TROCA_DATA_BASE: i64 = dias(2025, 2, 22);
+
+/// Intervalo de anos suportado por `DataCivil`
+pub(crate) const ANO_MIN: i32 = 1;
+pub(crate) const ANO_MAX: i32 = 9999;
+
+/// Tipo de data usado em toda a API: `chrono::NaiveDate` com a feature
+/// `chrono` (padrão) e `time::Date` com a feature `time`. Se as duas estiverem
+/// habilitadas, `chrono` tem prioridade.
+#[cfg(feature = "chrono")]
+pub type Data = chrono::NaiveDate;
+
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+pub type Data = time::Date;
+
+/// Data do calendário gregoriano usada quando nem `chrono` nem `time` estão
+/// habilitadas
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Data {
+    ano: i32,
+    mes: u8,
+    dia: u8,
+}
+
+/// Operações de calendário sobre `Data`, iguais para qualquer tipo de data
+/// selecionado pelas features. Apenas datas entre 01/01/0001 e 31/12/9999 são
+/// suportadas.
+pub trait DataCivil: Copy + Ord {
+    /// Retorna `None` para datas inexistentes ou fora do intervalo suportado
+    fn new(ano: i32, mes: u32, dia: u32) -> Option<Self>;
+
+    fn ano(&self) -> i32;
+
+    fn mes(&self) -> u32;
+
+    fn dia(&self) -> u32;
+
+    /// Data `dias` dias depois (ou antes, se negativo), ou `None` se o
+    /// resultado estiver fora do intervalo suportado
+    fn somar_dias(&self, dias: i64) -> Option<Self> {
+        from_dias(dias_desde_1970(self).checked_add(dias)?)
+    }
+
+    /// Quantidade de dias corridos de `outra` até esta data
+    fn dias_desde(&self, outra: Self) -> i64 {
+        dias_desde_1970(self) - dias_desde_1970(&outra)
+    }
+
+    /// Sábado ou domingo
+    fn is_fim_de_semana(&self) -> bool {
+        // 01/01/1970 foi uma quinta-feira
+        matches!(dias_desde_1970(self).rem_euclid(7), 2 | 3)
+    }
+}
+
+/// Dias de 01/01/1970 até a data
+const fn dias(ano: i32, mes: u32, dia: u32) -> i64 {
+    // Contando os anos a partir de março, o dia 29/02 fica no fim do ano
+    let ano = if mes <= 2 { ano as i64 - 1 } else { ano as i64 };
+    let era = ano.div_euclid(400);
+    let ano_da_era = ano.rem_euclid(400);
+    let dia_do_ano = (153 * ((mes as i64 + 9) % 12) + 2) / 5 + dia as i64 - 1;
+    let dia_da_era = ano_da_era * 365 + ano_da_era / 4 - ano_da_era / 100 + dia_do_ano;
+
+    era * 146_097 + dia_da_era - 719_468
+}
+
+fn dias_desde_1970<D: DataCivil>(data: &D) -> i64 {
+    dias(data.ano(), data.mes(), data.dia())
+}
+
+/// Inverso de `dias`, ou `None` fora do intervalo suportado
+fn from_dias<D: DataCivil>(dias_desde_1970: i64) -> Option<D> {
+    if !(dias(ANO_MIN, 1, 1)..=dias(ANO_MAX, 12, 31)).contains(&dias_desde_1970) {
+        return None;
+    }
+
+    let dias = dias_desde_1970 + 719_468;
+    let era = dias.div_euclid(146_097);
+    let dia_da_era = dias.rem_euclid(146_097);
+    let ano_da_era = (dia_da_era - dia_da_era / 1460 + dia_da_era / 36_524 - dia_da_era / 146_096) / 365;
+    let dia_do_ano = dia_da_era - (365 * ano_da_era + ano_da_era / 4 - ano_da_era / 100);
+    let mes = (5 * dia_do_ano + 2) / 153;
+    let dia = dia_do_ano - (153 * mes + 2) / 5 + 1;
+    let mes = if mes < 10 { mes + 3 } else { mes - 9 };
+    let ano = ano_da_era + era * 400 + if mes <= 2 { 1 } else { 0 };
+
+    D::new(ano as i32, mes as u32, dia as u32)
+}
+
+/// Lê datas no formato `AAAA-MM-DD`
+#[cfg(any(feature = "std", test, not(any(feature = "chrono", feature = "time"))))]
+pub(crate) fn parse_data(s: &str) -> Result<Data, BoletoError> {
+    let mut partes = s.splitn(3, '-');
+    let mut numero = || {
+        partes
+            .next()
+            .filter(|parte| !parte.is_empty() && parte.bytes().all(|c| c.is_ascii_digit()))
+            .and_then(|parte| parte.parse::<u32>().ok())
+    };
+
+    match (numero(), numero(), numero()) {
+        (Some(ano), Some(mes), Some(dia)) => {
+            Data::new(ano.try_into().map_err(|_| BoletoError::InvalidData)?, mes, dia)
+                .ok_or(BoletoError::InvalidData)
+        },
+        _ => Err(BoletoError::InvalidData),
+    }
+}
+
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+impl DataCivil for Data {
+    fn new(ano: i32, mes: u32, dia: u32) -> Option<Self> {
+        if !(ANO_MIN..=ANO_MAX).contains(&ano) {
+            return None;
+        }
+
+        let dias_no_mes = match mes {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if ano % 4 == 0 && (ano % 100 != 0 || ano % 400 == 0) => 29,
+            2 => 28,
+            _ => return None,
+        };
+
+        if dia == 0 || dia > dias_no_mes {
+            return None;
+        }
+
+        Some(Self { ano, mes: mes as u8, dia: dia as u8 })
+    }
+
+    fn ano(&self) -> i32 {
+        self.ano
+    }
+
+    fn mes(&self) -> u32 {
+        self.mes as u32
+    }
+
+    fn dia(&self) -> u32 {
+        self.dia as u32
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl DataCivil for Data {
+    fn new(ano: i32, mes: u32, dia: u32) -> Option<Self> {
+        (ANO_MIN..=ANO_MAX)
+            .contains(&ano)
+            .then(|| Self::from_ymd_opt(ano, mes, dia))
+            .flatten()
+    }
+
+    fn ano(&self) -> i32 {
+        chrono::Datelike::year(self)
+    }
+
+    fn mes(&self) -> u32 {
+        chrono::Datelike::month(self)
+    }
+
+    fn dia(&self) -> u32 {
+        chrono::Datelike::day(self)
+    }
+}
+
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+impl DataCivil for Data {
+    fn new(ano: i32, mes: u32, dia: u32) -> Option<Self> {
+        let mes = time::Month::try_from(u8::try_from(mes).ok()?).ok()?;
+
+        (ANO_MIN..=ANO_MAX)
+            .contains(&ano)
+            .then(|| Self::from_calendar_date(ano, mes, u8::try_from(dia).ok()?).ok())
+            .flatten()
+    }
+
+    fn ano(&self) -> i32 {
+        self.year()
+    }
+
+    fn mes(&self) -> u32 {
+        u8::from(self.month()).into()
+    }
+
+    fn dia(&self) -> u32 {
+        self.day().into()
+    }
+}
+
+/// Formata como `AAAA-MM-DD`, assim como `chrono::NaiveDate`
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+impl fmt::Display for Data {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.ano, self.mes, self.dia)
+    }
+}
+
+/// Lê datas no formato `AAAA-MM-DD`
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+impl FromStr for Data {
+    type Err = BoletoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_data(s)
+    }
+}
+
+#[cfg(all(feature = "serde", not(any(feature = "chrono", feature = "time"))))]
+impl Serialize for Data {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(all(feature = "serde", not(any(feature = "chrono", feature = "time"))))]
+struct DataVisitor;
+
+#[cfg(all(feature = "serde", not(any(feature = "chrono", feature = "time"))))]
+impl<'de> Visitor<'de> for DataVisitor {
+    type Value = Data;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("data no formato AAAA-MM-DD")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        value.parse().map_err(E::custom)
+    }
+}
+
+#[cfg(all(feature = "serde", not(any(feature = "chrono", feature = "time"))))]
+impl<'de> Deserialize<'de> for Data {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de> {
+        deserializer.deserialize_str(DataVisitor)
+    }
+}
+
+/// Data representada pelo fator segundo a política padrão (de 01/01/2010 a
+/// 22/08/2034). Fatores menores que 1000 não representam nenhuma data.
+pub fn fator_vencimento_to_date(fator: u16) -> Option<Data> {
+    if fator < 1000 {
+        return None;
+    }
+
+    let inicio = DATA_BASE + FATOR_VENC_2010 as i64;
+    let dias = DATA_BASE + fator as i64;
+
+    from_dias(if dias < inicio { dias + CICLO } else { dias })
+}
+
+/// Define quais datas são plausíveis para um fator de vencimento.
+///
+/// Como o fator reinicia em 1000 a cada 9000 dias, um mesmo fator representa
+/// várias datas. São consideradas apenas as datas dentro da janela
+/// `[data_referencia - dias_antes, data_referencia + dias_depois]`; uma janela
+/// com mais de 9000 dias pode conter mais de uma data para o mesmo fator.
+///
+/// A política padrão cobre de 01/01/2010 a 22/08/2034, resolvendo cada fator
+/// para exatamente uma data.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FatorVencimentoPolicy {
+    pub data_referencia: Data,
+    pub dias_antes: u32,
+    pub dias_depois: u32,
+}
+
+#[cfg(feature = "std")]
+impl Default for FatorVencimentoPolicy {
+    fn default() -> Self {
+        Self {
+            data_referencia: from_dias(DATA_BASE + FATOR_VENC_2010 as i64).expect("01/01/2010 é representável"),
+            dias_antes: 0,
+            dias_depois: (CICLO - 1) as u32,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl FatorVencimentoPolicy {
+    pub fn new(data_referencia: Data, dias_antes: u32, dias_depois: u32) -> Self {
+        Self { data_referencia, dias_antes, dias_depois }
+    }
+
+    /// Política que aceita qualquer data representável, ou seja, todas as
+    /// datas de cada fator
+    #[cfg(feature = "serde")]
+    pub(crate) fn irrestrita() -> Self {
+        Self::new(Data::new(ANO_MIN, 1, 1).expect("01/01/0001 é representável"), 0, u32::MAX)
+    }
+
+    /// Primeira e última data aceitas pela política, limitadas ao intervalo
+    /// suportado por `DataCivil`
+    pub fn janela(&self) -> (Data, Data) {
+        let limite = |dias| from_dias(dias).expect("limites do intervalo são representáveis");
+
+        (
+            self.data_referencia
+                .somar_dias(-i64::from(self.dias_antes))
+                .unwrap_or_else(|| limite(dias(ANO_MIN, 1, 1))),
+            self.data_referencia
+                .somar_dias(self.dias_depois.into())
+                .unwrap_or_else(|| limite(dias(ANO_MAX, 12, 31))),
+        )
+    }
+
+    /// Todas as datas dentro da janela representadas pelo fator, em ordem
+    /// cronológica. Fatores menores que 1000 não representam nenhuma data.
+    pub fn candidatas(&self, fator: u16) -> Vec<Data> {
+        if fator < 1000 {
+            return Vec::new();
+        }
+
+        let (inicio, fim) = self.janela();
+
+        (0..)
+            .map(|ciclo| from_dias::<Data>(DATA_BASE + fator as i64 + ciclo * CICLO))
+            .map_while(|date| date.filter(|date| *date <= fim))
+            .filter(|date| *date >= inicio)
+            .collect()
+    }
+
+    /// Data mais próxima da data de referência entre as candidatas
+    pub fn resolve(&self, fator: u16) -> Option<Data> {
+        self.candidatas(fator)
+            .into_iter()
+            .min_by_key(|date| date.dias_desde(self.data_referencia).abs())
+    }
+}
+
+/// Calcula o fator de vencimento de uma data, usando a data base vigente na
+/// data informada. Datas fora do intervalo de 03/07/2000 a 13/10/2049 não
+/// podem ser representadas.
+pub fn date_to_fator_vencimento(date: Data) -> Result<u16, BoletoError> {
+    let dias = dias_desde_1970(&date);
+    let base = if dias < TROCA_DATA_BASE { DATA_BASE } else { PROXIMA_DATA_BASE };
+
+    match u16::try_from(dias - base) {
+        Ok(fator) if (1000..=9999).contains(&fator) => Ok(fator),
+        _ => Err(BoletoError::InvalidDataVencimento),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(y: i32, m: u32, d: u32) -> Data {
+        Data::new(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn create_data() {
+        assert_eq!(Data::new(2024, 2, 29), Some(data(2024, 2, 29)));
+        assert_eq!(Data::new(2000, 2, 29), Some(data(2000, 2, 29)));
+        assert_eq!(Data::new(2100, 2, 29), None);
+        assert_eq!(Data::new(2023, 2, 29), None);
+        assert_eq!(Data::new(2023, 4, 31), None);
+        assert_eq!(Data::new(2023, 13, 1), None);
+        assert_eq!(Data::new(2023, 1, 0), None);
+        assert_eq!(Data::new(0, 12, 31), None);
+        assert_eq!(Data::new(10_000, 1, 1), None);
+        assert_eq!(data(2025, 2, 3).to_string(), "2025-02-03");
+        assert_eq!(parse_data("2025-02-03"), Ok(data(2025, 2, 3)));
+
+        for invalid in ["2025-02-30", "2025-2", "2025-02-03-", "+2025-02-03", "ontem", ""] {
+            assert_eq!(parse_data(invalid), Err(BoletoError::InvalidData), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn calculate_with_data_correctly() {
+        assert_eq!(data(2024, 2, 28).somar_dias(1), Some(data(2024, 2, 29)));
+        assert_eq!(data(2024, 12, 31).somar_dias(1), Some(data(2025, 1, 1)));
+        assert_eq!(data(2025, 3, 1).somar_dias(-1), Some(data(2025, 2, 28)));
+        assert_eq!(data(9999, 12, 31).somar_dias(1), None);
+        assert_eq!(data(1, 1, 1).somar_dias(-1), None);
+        assert_eq!(data(1, 1, 1).somar_dias(i64::MAX), None);
+        assert_eq!(data(2025, 2, 22).dias_desde(data(1997, 10, 7)), 10_000);
+
+        // 22/02/2025 é um sábado
+        assert!(data(2025, 2, 22).is_fim_de_semana());
+        assert!(data(2025, 2, 23).is_fim_de_semana());
+        assert!(!data(2025, 2, 24).is_fim_de_semana());
+        assert!(!data(2025, 2, 21).is_fim_de_semana());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn data_is_chrono_naive_date() {
+        let naive_date: chrono::NaiveDate = data(2024, 2, 29);
+
+        assert_eq!(naive_date, chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+        assert_eq!(Data::new(-1, 1, 1), None);
+        assert_eq!(chrono::NaiveDate::MAX.somar_dias(-1), None);
+    }
+
+    #[cfg(all(feature = "time", not(feature = "chrono")))]
+    #[test]
+    fn data_is_time_date() {
+        let date: time::Date = data(2024, 2, 29);
+
+        assert_eq!(date, time::Date::from_calendar_date(2024, time::Month::February, 29).unwrap());
+        assert_eq!(Data::new(-1, 1, 1), None);
+        assert_eq!(time::Date::MIN.somar_dias(1), None);
+    }
+
+    #[test]
+    fn convert_fator_vencimento_to_data_correctly() {
+        assert_eq!(fator_vencimento_to_date(FATOR_VENC_2010), Some(data(2010, 1, 1)));
+        assert_eq!(fator_vencimento_to_date(FATOR_VENC_2010 - 1), Some(data(2034, 8, 22)));
+        assert_eq!(fator_vencimento_to_date(9999), Some(data(2025, 2, 21)));
+        assert_eq!(fator_vencimento_to_date(1000), Some(data(2025, 2, 22)));
+        assert_eq!(fator_vencimento_to_date(1667), Some(data(2026, 12, 21)));
+        assert_eq!(fator_vencimento_to_date(999), None);
+
+        assert_eq!(date_to_fator_vencimento(data(2000, 7, 3)).unwrap(), 1000);
+        assert_eq!(date_to_fator_vencimento(data(2025, 2, 21)).unwrap(), 9999);
+        assert_eq!(date_to_fator_vencimento(data(2025, 2, 22)).unwrap(), 1000);
+        assert_eq!(date_to_fator_vencimento(data(2049, 10, 13)).unwrap(), 9999);
+        assert!(date_to_fator_vencimento(data(2000, 7, 2)).is_err());
+        assert!(date_to_fator_vencimento(data(2049, 10, 14)).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn data_matches_default_policy() {
+        let policy = FatorVencimentoPolicy::default();
+
+        for fator in 0..=9999 {
+            assert_eq!(fator_vencimento_to_date(fator), policy.resolve(fator), "{fator}");
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn resolve_fator_vencimento_with_policy_correctly() {
+        let policy = FatorVencimentoPolicy::default();
+
+        assert_eq!(policy.resolve(FATOR_VENC_2010), Some(data(2010, 1, 1)));
+        assert_eq!(policy.resolve(FATOR_VENC_2010 + 1), Some(data(2010, 1, 2)));
+        assert_eq!(policy.resolve(FATOR_VENC_2010 - 1), Some(data(2034, 8, 22)));
+        assert_eq!(policy.resolve(4789), Some(data(2010, 11, 17)));
+        assert_eq!(policy.resolve(9999), Some(data(2025, 2, 21)));
+        assert_eq!(policy.resolve(1000), Some(data(2025, 2, 22)));
+        assert_eq!(policy.resolve(1002), Some(data(2025, 2, 24)));
+        assert_eq!(policy.resolve(1667), Some(data(2026, 12, 21)));
+        assert_eq!(policy.resolve(999), None);
+        assert_eq!(policy.resolve(0), None);
+
+        for fator in 1000..=9999 {
+            assert_eq!(policy.candidatas(fator).len(), 1, "{fator}");
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn return_every_candidate_date() {
+        let policy = FatorVencimentoPolicy::new(data(2025, 2, 22), 6000, 6000);
+
+        assert_eq!(policy.candidatas(4468), vec![data(2009, 12, 31), data(2034, 8, 22)]);
+        assert_eq!(policy.resolve(4468), Some(data(2034, 8, 22)));
+        assert_eq!(policy.candidatas(9999), vec![data(2025, 2, 21)]);
+
+        let policy = FatorVencimentoPolicy::new(data(2025, 2, 22), 30, 30);
+
+        assert_eq!(policy.janela(), (data(2025, 1, 23), data(2025, 3, 24)));
+        assert_eq!(policy.candidatas(9990), vec![data(2025, 2, 12)]);
+        assert_eq!(policy.candidatas(1010), vec![data(2025, 3, 4)]);
+        assert!(policy.candidatas(4468).is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn convert_data_to_fator_vencimento_correctly() {
+        assert_eq!(date_to_fator_vencimento(data(2010, 1, 2)).unwrap(), FATOR_VENC_2010 + 1);
+        assert_eq!(date_to_fator_vencimento(data(2034, 8, 22)).unwrap(), FATOR_VENC_2010 - 1);
+        assert_eq!(date_to_fator_vencimento(data(2010, 11, 17)).unwrap(), 4789);
+        assert_eq!(date_to_fator_vencimento(data(2025, 2, 21)).unwrap(), 9999);
+        assert_eq!(date_to_fator_vencimento(data(2025, 2, 22)).unwrap(), 1000);
+        assert_eq!(date_to_fator_vencimento(data(2025, 2, 24)).unwrap(), 1002);
+        assert_eq!(date_to_fator_vencimento(data(2026, 12, 21)).unwrap(), 1667);
+        assert_eq!(date_to_fator_vencimento(data(2000, 7, 3)).unwrap(), 1000);
+        assert_eq!(date_to_fator_vencimento(data(2049, 10, 13)).unwrap(), 9999);
+
+        for invalid in [data(2000, 7, 2), data(1990, 1, 1), data(2049, 10, 14)] {
+            assert!(matches!(
+                date_to_fator_vencimento(invalid),
+                Err(BoletoError::InvalidDataVencimento),
+            ));
+        }
+    }
+}
@@ -1,20 +1,72 @@
+//! Leitura, validação e geração de boletos no padrão Febraban.
+//!
+//! Sem a feature `std` a crate é `no_std` e não aloca. Nesse caso estão
+//! disponíveis:
+//!
+//! - `CodBarras::parse`, `cobranca::CodBarras::parse` e
+//!   `arrecadacao::CodBarras::parse`, que aceitam o código de barras ou a
+//!   linha digitável e validam os dígitos verificadores;
+//! - a leitura de cada campo a partir de `cobranca::CodBarras` (banco, moeda,
+//!   fator e data de vencimento, valor e campo livre em dígitos) e de
+//!   `arrecadacao::CodBarras` (segmento, tipo de valor, valor e convênio);
+//! - a conversão entre código de barras e linha digitável;
+//! - `fator_vencimento::Data` e `DataCivil`, os builders (`build_cod_barras`) e
+//!   `render::itf`.
+//!
+//! `Boleto`, `Cobranca` e `Arrecadacao` dependem de `std`, pois incluem os
+//! dados de bancos e convênios, as datas candidatas do fator de vencimento e
+//! a decodificação do campo livre.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
 mod utils;
+#[cfg(feature = "std")]
+pub mod bancos;
 pub mod cobranca;
 pub mod arrecadacao;
 pub mod builder;
+#[cfg(feature = "std")]
+pub mod convenios;
+#[cfg(feature = "std")]
+pub mod campo_livre;
+pub mod valor;
+pub mod fator_vencimento;
+#[cfg(feature = "std")]
+pub mod calendario;
+#[cfg(feature = "std")]
+pub mod encargos;
+#[cfg(feature = "std")]
+pub mod correcao;
+#[cfg(feature = "std")]
+pub mod diagnostico;
+#[cfg(feature = "std")]
+pub mod normalizacao;
+#[cfg(feature = "std")]
+pub mod completar;
+pub mod render;
 
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::de::value::MapAccessDeserializer;
+#[cfg(feature = "serde")]
+use serde::de::{self, MapAccess, Visitor};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use thiserror::Error;
 
 use arrecadacao::CodBarras as CodBarrasArr;
 use cobranca::CodBarras as CodBarrasCob;
 
+#[cfg(feature = "std")]
+use crate::campo_livre::Decoders;
+#[cfg(feature = "std")]
 use crate::cobranca::Cobranca;
+#[cfg(feature = "std")]
 use crate::arrecadacao::Arrecadacao;
+#[cfg(feature = "std")]
+use crate::diagnostico::{Campo, Diagnostic};
 
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum BoletoError {
     #[error("deve conter apenas números")]
     NumbersOnly,
@@ -36,18 +88,120 @@ pub enum BoletoError {
     InvalidSegmento,
     #[error("tipo de valor inválido")]
     InvalidTipoValor,
+    #[error("dígito verificador do campo livre inválido")]
+    InvalidDigitoVerificadorCampoLivre,
+    #[error("campo livre inválido")]
+    InvalidCampoLivre,
+    #[error("valor inválido")]
+    InvalidValor,
+    #[error("convênio inválido para o segmento")]
+    InvalidConvenio,
+    #[error("valor excede a quantidade de dígitos do código de barras")]
+    ValorOverflow,
+    #[error("data de vencimento fora do intervalo representável pelo fator de vencimento")]
+    InvalidDataVencimento,
+    #[error("código do banco deve ter no máximo 3 dígitos")]
+    InvalidCodBanco,
+    #[error("data inválida")]
+    InvalidData,
+    #[error("dimensões do código de barras fora da especificação")]
+    InvalidDimensoes,
+    #[error("arquivo de feriados inválido{}", NaLinha(.linha))]
+    InvalidFeriados { linha: Option<u64> },
 }
 
+/// Complemento " na linha N" das mensagens de erro, quando a linha é conhecida
+struct NaLinha<'a>(&'a Option<u64>);
 
-#[derive(Debug, Serialize)]
-#[serde(tag = "tipo", content = "dados")]
+impl core::fmt::Display for NaLinha<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Some(linha) => write!(f, " na linha {linha}"),
+            None => Ok(()),
+        }
+    }
+}
+
+
+/// Código de barras de um boleto de cobrança ou de arrecadação, sem os dados
+/// que dependem de `std` (ver `Boleto`)
+#[derive(Debug, PartialEq, Eq)]
+pub enum CodBarras {
+    Arrecadacao(CodBarrasArr),
+    Cobranca(CodBarrasCob),
+}
+
+impl CodBarras {
+    /// Lê um código de barras ou uma linha digitável de qualquer tipo de
+    /// boleto (ver `cobranca::CodBarras::parse` e
+    /// `arrecadacao::CodBarras::parse`)
+    pub fn parse(value: &[u8]) -> Result<Self, BoletoError> {
+        match value.first() {
+            None => Err(BoletoError::InvalidLength),
+            Some(b'8') => Ok(Self::Arrecadacao(CodBarrasArr::parse(value)?)),
+            _ => Ok(Self::Cobranca(CodBarrasCob::parse(value)?)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tipo", content = "dados"))]
 pub enum Boleto {
+    #[cfg_attr(feature = "serde", serde(rename = "arrecadacao"))]
+    Arrecadacao(Arrecadacao),
+    #[cfg_attr(feature = "serde", serde(rename = "cobranca"))]
+    Cobranca(Cobranca),
+}
+
+/// Forma serializada de `Boleto`, com o tipo de boleto e os dados
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(tag = "tipo", content = "dados")]
+enum BoletoSerializado {
     #[serde(rename = "arrecadacao")]
     Arrecadacao(Arrecadacao),
     #[serde(rename = "cobranca")]
     Cobranca(Cobranca),
 }
 
+#[cfg(feature = "serde")]
+struct BoletoVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for BoletoVisitor {
+    type Value = Boleto;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("código de barras, linha digitável ou boleto serializado")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Boleto::new(value.as_bytes()).map_err(E::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        Ok(match BoletoSerializado::deserialize(MapAccessDeserializer::new(map))? {
+            BoletoSerializado::Arrecadacao(dados) => Boleto::Arrecadacao(dados),
+            BoletoSerializado::Cobranca(dados) => Boleto::Cobranca(dados),
+        })
+    }
+}
+
+/// Aceita a forma serializada (`{"tipo": ..., "dados": ...}`) ou apenas o
+/// código de barras ou a linha digitável, validados como em `Cobranca` e
+/// `Arrecadacao`.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Boleto {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de> {
+        deserializer.deserialize_any(BoletoVisitor)
+    }
+}
+
+#[cfg(feature = "std")]
 impl std::fmt::Display for Boleto {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", match self {
@@ -57,6 +211,7 @@ impl std::fmt::Display for Boleto {
     }
 }
 
+#[cfg(feature = "std")]
 impl Boleto {
     pub fn new(value: &[u8]) -> Result<Self, BoletoError> {
         match value.first() {
@@ -66,6 +221,26 @@ impl Boleto {
         }
     }
 
+    /// Assim como `new`, mas decodifica o campo livre com os decodificadores
+    /// informados em vez do registro global (ver `campo_livre::registrar`)
+    pub fn new_with(decoders: &Decoders, value: &[u8]) -> Result<Self, BoletoError> {
+        match value.first() {
+            None => Err(BoletoError::InvalidLength),
+            Some(b'8') => Ok(Boleto::Arrecadacao(Arrecadacao::new(value)?)),
+            _ => Ok(Boleto::Cobranca(Cobranca::new_with(decoders, value)?)),
+        }
+    }
+
+    /// Assim como `new`, mas retorna todos os problemas encontrados em vez de
+    /// apenas o primeiro
+    pub fn diagnose(value: &[u8]) -> Result<Self, Vec<Diagnostic>> {
+        match value.first() {
+            None => Err(vec![Diagnostic::new(Campo::Tamanho, value, 0..0, BoletoError::InvalidLength)]),
+            Some(b'8') => Ok(Boleto::Arrecadacao(Arrecadacao::diagnose(value)?)),
+            _ => Ok(Boleto::Cobranca(Cobranca::diagnose(value)?)),
+        }
+    }
+
     pub fn calculate_digito_verificador(value: &[u8]) -> Result<u8, BoletoError> {
         match value.first() {
             None => Err(BoletoError::InvalidLength),
@@ -75,10 +250,11 @@ impl Boleto {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
-    use chrono::NaiveDate;
+    use crate::fator_vencimento::{Data, DataCivil};
     use crate::Boleto;
+    use crate::valor::Valor;
 
     #[test]
     fn valid_barcode() {
@@ -90,9 +266,9 @@ mod tests {
             Boleto::Cobranca(cob) => {
                 assert_eq!(
                     cob.data_vencimento,
-                    Some(NaiveDate::from_ymd_opt(2022, 5, 10).unwrap())
+                    Some(Data::new(2022, 5, 10).unwrap())
                 );
-                assert_eq!(cob.valor, Some(214.03));
+                assert_eq!(cob.valor, Some(Valor::from_centavos(21403)));
             },
             _ => panic!("Should be Cobranca"),
         }
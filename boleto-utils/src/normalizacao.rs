@@ -0,0 +1,186 @@
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::{Boleto, BoletoError};
+
+/// Quantidade de dígitos antes de cada separador da linha digitável de
+/// cobrança e o separador esperado:
+/// `AAABC.CCCCX DDDDD.DDDDDY EEEEE.EEEEEZ K UUUUVVVVVVVVVV`
+const SEPARADORES_COBRANCA: [(usize, char); 7] = [
+    (5, '.'), (10, ' '), (15, '.'), (21, ' '), (26, '.'), (32, ' '), (33, ' '),
+];
+
+/// Quantidade de dígitos antes de cada separador da linha digitável de
+/// arrecadação e o separador esperado:
+/// `NNNNNNNNNNN-D NNNNNNNNNNN-D NNNNNNNNNNN-D NNNNNNNNNNN-D`
+const SEPARADORES_ARRECADACAO: [(usize, char); 7] = [
+    (11, '-'), (12, ' '), (23, '-'), (24, ' '), (35, '-'), (36, ' '), (47, '-'),
+];
+
+/// Ajuste feito na entrada antes da validação. As posições são contadas em
+/// caracteres a partir do início da entrada original.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tipo"))]
+pub enum Ajuste {
+    /// Separador ('.', '-' ou espaço) removido de uma posição esperada
+    #[cfg_attr(feature = "serde", serde(rename = "separador"))]
+    Separador { posicao: usize, caractere: char },
+    /// Caractere confundido com um dígito por OCR ('O' por '0', 'l' ou 'I'
+    /// por '1')
+    #[cfg_attr(feature = "serde", serde(rename = "ocr"))]
+    Ocr { posicao: usize, lido: char, corrigido: char },
+}
+
+fn normalizar(input: &str, corrigir_ocr: bool) -> Result<(Vec<u8>, Vec<Ajuste>), BoletoError> {
+    let inicio = input.chars().take_while(|c| c.is_whitespace()).count();
+    let input = input.trim();
+
+    let mut digitos = Vec::with_capacity(input.len());
+    let mut ajustes = Vec::new();
+    // Quantidade de dígitos antes do último separador removido, para aceitar
+    // apenas um separador entre cada grupo
+    let mut ultimo_separador = None;
+
+    for (i, c) in input.chars().enumerate() {
+        let posicao = inicio + i;
+
+        let digito = match c {
+            '0'..='9' => c,
+            'O' | 'o' if corrigir_ocr => '0',
+            'l' | 'I' if corrigir_ocr => '1',
+            '.' | '-' | ' ' => {
+                let separadores = match digitos.first() {
+                    Some(b'8') => SEPARADORES_ARRECADACAO.as_slice(),
+                    _ => SEPARADORES_COBRANCA.as_slice(),
+                };
+
+                if !separadores.contains(&(digitos.len(), c)) || ultimo_separador == Some(digitos.len()) {
+                    return Err(BoletoError::NumbersOnly);
+                }
+
+                ultimo_separador = Some(digitos.len());
+                ajustes.push(Ajuste::Separador { posicao, caractere: c });
+                continue;
+            },
+            _ => return Err(BoletoError::NumbersOnly),
+        };
+
+        if digito != c {
+            ajustes.push(Ajuste::Ocr { posicao, lido: c, corrigido: digito });
+        }
+
+        digitos.push(digito as u8);
+    }
+
+    Ok((digitos, ajustes))
+}
+
+impl Boleto {
+    /// Assim como `from_str`, aceita a linha digitável formatada, e também
+    /// corrige caracteres comumente confundidos por OCR. Retorna os ajustes
+    /// feitos na entrada junto com o boleto.
+    pub fn parse_lenient(input: &str) -> Result<(Self, Vec<Ajuste>), BoletoError> {
+        let (digitos, ajustes) = normalizar(input, true)?;
+
+        Ok((Self::new(&digitos)?, ajustes))
+    }
+}
+
+/// Aceita código de barras ou linha digitável, ignorando espaços no início e
+/// no fim e os separadores da linha digitável formatada (ex.:
+/// `75691.43436 01033.723402 00149.330011 6 90380000250000`). Cada separador
+/// deve ser o esperado para a posição e aparecer apenas uma vez.
+impl FromStr for Boleto {
+    type Err = BoletoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (digitos, _) = normalizar(s, false)?;
+
+        Self::new(&digitos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_formatted_cobranca() {
+        let boleto: Boleto = " 75691.43436 01033.723402 00149.330011 6 90380000250000\n".parse().unwrap();
+
+        assert!(matches!(
+            boleto,
+            Boleto::Cobranca(c) if c.linha_digitavel.as_str() == "75691434360103372340200149330011690380000250000"
+        ));
+
+        assert!("75691434360103372340200149330011690380000250000".parse::<Boleto>().is_ok());
+        assert!("75696903800002500001434301033723400014933001".parse::<Boleto>().is_ok());
+    }
+
+    #[test]
+    fn parse_formatted_arrecadacao() {
+        let boleto: Boleto = "83655555555-3 55556666777-3 77777777777-5 77777777777-5".parse().unwrap();
+
+        assert!(matches!(
+            boleto,
+            Boleto::Arrecadacao(a) if a.linha_digitavel.as_str() == "836555555553555566667773777777777775777777777775"
+        ));
+    }
+
+    #[test]
+    fn reject_misplaced_separators() {
+        let inputs = [
+            "7569.143436 01033.723402 00149.330011 6 90380000250000",
+            "75691.43436 01033.723402 00149.330011 6 9038000025.0000",
+            "8365555555-53 55556666777-3 77777777777-5 77777777777-5",
+            "756914343 60103372340200149330011690380000250000",
+            // Separadores repetidos ou de outro tipo entre os grupos
+            "75691...43436 01033.723402 00149.330011 6 90380000250000",
+            "75691.43436 - 01033.723402 00149.330011 6 90380000250000",
+            "75691.43436  01033.723402 00149.330011 6 90380000250000",
+            "75691 43436 01033.723402 00149.330011 6 90380000250000",
+            "75691.43436.01033.723402 00149.330011 6 90380000250000",
+            "75691.43436 01033.723402 00149.330011 6  90380000250000",
+            "83655555555--3 55556666777-3 77777777777-5 77777777777-5",
+            "83655555555-3 - 55556666777-3 77777777777-5 77777777777-5",
+            "83655555555 3 55556666777-3 77777777777-5 77777777777-5",
+            "83655555555-3-55556666777-3 77777777777-5 77777777777-5",
+        ];
+
+        for input in inputs {
+            assert!(input.parse::<Boleto>().is_err(), "{input}");
+        }
+
+        assert!(matches!(
+            "75691.43436 01033.723402 00149.330011 6 9038000025000O".parse::<Boleto>(),
+            Err(BoletoError::NumbersOnly),
+        ));
+    }
+
+    #[test]
+    fn parse_lenient_correctly() {
+        let (boleto, ajustes) = Boleto::parse_lenient("75691.43436 O1033.723402 0Ol49.33OOlI 6 90380000250000").unwrap();
+
+        assert!(matches!(boleto, Boleto::Cobranca(_)));
+        assert_eq!(
+            ajustes.iter().filter(|a| matches!(a, Ajuste::Ocr { .. })).copied().collect::<Vec<_>>(),
+            vec![
+                Ajuste::Ocr { posicao: 12, lido: 'O', corrigido: '0' },
+                Ajuste::Ocr { posicao: 26, lido: 'O', corrigido: '0' },
+                Ajuste::Ocr { posicao: 27, lido: 'l', corrigido: '1' },
+                Ajuste::Ocr { posicao: 33, lido: 'O', corrigido: '0' },
+                Ajuste::Ocr { posicao: 34, lido: 'O', corrigido: '0' },
+                Ajuste::Ocr { posicao: 35, lido: 'l', corrigido: '1' },
+                Ajuste::Ocr { posicao: 36, lido: 'I', corrigido: '1' },
+            ],
+        );
+        assert_eq!(ajustes.iter().filter(|a| matches!(a, Ajuste::Separador { .. })).count(), 7);
+        assert_eq!(ajustes[0], Ajuste::Separador { posicao: 5, caractere: '.' });
+
+        let (_, ajustes) = Boleto::parse_lenient("75691434360103372340200149330011690380000250000").unwrap();
+        assert!(ajustes.is_empty());
+    }
+}
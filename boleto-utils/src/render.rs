@@ -0,0 +1,5 @@
+//! Geração da representação gráfica dos códigos de barras.
+
+pub mod itf;
+#[cfg(feature = "std")]
+pub mod svg;
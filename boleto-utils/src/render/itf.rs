@@ -0,0 +1,241 @@
+//! Codificação Interleaved 2 of 5 (ITF) dos códigos de barras de 44 dígitos.
+//!
+//! Os dígitos são codificados em pares: o primeiro nas barras e o segundo nos
+//! espaços entre elas. As dimensões seguem o item 2.3.3 de
+//! `documents/Doc5175Bloqueto.pdf`.
+
+use crate::BoletoError;
+
+/// Comprimento máximo do código de barras, sem as zonas de silêncio, em mm
+pub const COMPRIMENTO_MAX: f64 = 103.0;
+/// Altura do código de barras, em mm
+pub const ALTURA: f64 = 13.0;
+/// Zona de silêncio mínima antes e depois do código de barras, em mm
+pub const ZONA_SILENCIO_MIN: f64 = 5.0;
+/// Proporções entre a barra larga e a estreita aceitas pelo padrão ITF
+pub const PROPORCAO_MIN: f64 = 2.25;
+pub const PROPORCAO_MAX: f64 = 3.0;
+
+const DIGITOS: usize = 44;
+const INICIO: [bool; 4] = [false; 4];
+const FIM: [bool; 3] = [true, false, false];
+
+/// Barras largas (`true`) e estreitas (`false`) de cada dígito
+const PADROES: [[bool; 5]; 10] = [
+    [false, false, true, true, false],
+    [true, false, false, false, true],
+    [false, true, false, false, true],
+    [true, true, false, false, false],
+    [false, false, true, false, true],
+    [true, false, true, false, false],
+    [false, true, true, false, false],
+    [false, false, false, true, true],
+    [true, false, false, true, false],
+    [false, true, false, true, false],
+];
+
+/// Dimensões usadas na codificação, em mm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Config {
+    pub largura_estreita: f64,
+    pub proporcao_larga: f64,
+    pub zona_silencio: f64,
+}
+
+impl Default for Config {
+    /// Barra estreita de 0,254mm na proporção 1:3, o que resulta em 102,87mm
+    fn default() -> Self {
+        Self {
+            largura_estreita: 0.254,
+            proporcao_larga: 3.0,
+            zona_silencio: ZONA_SILENCIO_MIN,
+        }
+    }
+}
+
+impl Config {
+    pub fn largura_larga(&self) -> f64 {
+        self.largura_estreita * self.proporcao_larga
+    }
+
+    /// Comprimento do código de barras sem as zonas de silêncio
+    pub fn comprimento(&self) -> f64 {
+        // Cada dígito tem 3 barras estreitas e 2 largas, o início tem 4
+        // estreitas e o fim 2 estreitas e 1 larga
+        let estreitas = (DIGITOS * 3 + 4 + 2) as f64;
+        let largas = (DIGITOS * 2 + 1) as f64;
+
+        estreitas * self.largura_estreita + largas * self.largura_larga()
+    }
+
+    pub fn validate(&self) -> Result<(), BoletoError> {
+        let valido = self.largura_estreita > 0.0
+            && (PROPORCAO_MIN..=PROPORCAO_MAX).contains(&self.proporcao_larga)
+            && self.zona_silencio >= ZONA_SILENCIO_MIN
+            && self.comprimento() <= COMPRIMENTO_MAX;
+
+        if valido {
+            Ok(())
+        } else {
+            Err(BoletoError::InvalidDimensoes)
+        }
+    }
+}
+
+/// Uma barra ou um espaço e sua largura em mm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Modulo {
+    pub barra: bool,
+    pub largura: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Itf {
+    modulos: [Modulo; Itf::MODULOS],
+    config: Config,
+}
+
+impl Itf {
+    /// Zonas de silêncio, início, 5 módulos por dígito e fim
+    pub const MODULOS: usize = 2 + INICIO.len() + DIGITOS * 5 + FIM.len();
+
+    /// Aceita tanto `cobranca::CodBarras` quanto `arrecadacao::CodBarras`
+    pub fn new(cod_barras: &[u8; DIGITOS], config: Config) -> Result<Self, BoletoError> {
+        config.validate()?;
+
+        if !cod_barras.iter().all(|c| c.is_ascii_digit()) {
+            return Err(BoletoError::NumbersOnly);
+        }
+
+        let largura = |larga: bool| if larga {
+            config.largura_larga()
+        } else {
+            config.largura_estreita
+        };
+
+        let zona_silencio = Modulo { barra: false, largura: config.zona_silencio };
+        let mut modulos = [zona_silencio; Self::MODULOS];
+        let mut barras = modulos[1..Self::MODULOS - 1]
+            .iter_mut()
+            .zip([true, false].iter().cycle());
+
+        let larguras = cod_barras
+            .chunks(2)
+            .flat_map(|par| {
+                let barras = PADROES[(par[0] - b'0') as usize];
+                let espacos = PADROES[(par[1] - b'0') as usize];
+                barras.into_iter().zip(espacos).flat_map(|(b, e)| [b, e])
+            });
+
+        for larga in INICIO.into_iter().chain(larguras).chain(FIM) {
+            let (modulo, barra) = barras.next().unwrap();
+            *modulo = Modulo { barra: *barra, largura: largura(larga) };
+        }
+
+        Ok(Self { modulos, config })
+    }
+
+    pub fn modulos(&self) -> &[Modulo] {
+        &self.modulos
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Largura total, incluindo as zonas de silêncio
+    pub fn largura(&self) -> f64 {
+        self.config.comprimento() + 2.0 * self.config.zona_silencio
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{arrecadacao, cobranca};
+
+    const COBRANCA: &[u8] = b"10499898100000214032006561000100040099726390";
+
+    fn aproximado(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn default_config_follows_febraban() {
+        let config = Config::default();
+
+        assert_eq!(config.validate(), Ok(()));
+        assert!(aproximado(config.comprimento(), 102.87));
+    }
+
+    #[test]
+    fn invalid_config() {
+        let invalidas = [
+            Config { largura_estreita: 0.0, ..Config::default() },
+            Config { largura_estreita: 0.3, ..Config::default() },
+            Config { proporcao_larga: 2.0, ..Config::default() },
+            Config { proporcao_larga: 3.5, ..Config::default() },
+            Config { zona_silencio: 4.9, ..Config::default() },
+        ];
+
+        for config in invalidas {
+            assert_eq!(config.validate(), Err(BoletoError::InvalidDimensoes));
+        }
+
+        let cod_barras = cobranca::CodBarras::new(COBRANCA).unwrap();
+        assert_eq!(
+            Itf::new(&cod_barras, invalidas[0]),
+            Err(BoletoError::InvalidDimensoes),
+        );
+    }
+
+    #[test]
+    fn encode_cobranca() {
+        let cod_barras = cobranca::CodBarras::new(COBRANCA).unwrap();
+        let itf = Itf::new(&cod_barras, Config::default()).unwrap();
+        let modulos = itf.modulos();
+
+        assert_eq!(modulos.len(), Itf::MODULOS);
+        assert_eq!(modulos[0], Modulo { barra: false, largura: 5.0 });
+        assert_eq!(modulos[Itf::MODULOS - 1], Modulo { barra: false, largura: 5.0 });
+        assert!(modulos[1..Itf::MODULOS - 1]
+            .iter()
+            .enumerate()
+            .all(|(i, m)| m.barra == (i % 2 == 0)));
+
+        let larguras = |modulos: &[Modulo]| -> [bool; 10] {
+            let mut larguras = [false; 10];
+            for (larga, m) in larguras.iter_mut().zip(modulos) {
+                *larga = m.largura > 0.254;
+            }
+            larguras
+        };
+
+        // Início: barra, espaço, barra, espaço estreitos
+        assert!(modulos[1..5].iter().all(|m| m.largura == 0.254));
+        // "10": barras de 1 (WNNNW) e espaços de 0 (NNWWN)
+        assert_eq!(
+            larguras(&modulos[5..15]),
+            [true, false, false, false, false, true, false, true, true, false],
+        );
+        // Fim: barra larga, espaço e barra estreitos
+        let fim = &modulos[Itf::MODULOS - 4..Itf::MODULOS - 1];
+        assert_eq!(fim.iter().map(|m| m.largura).collect::<Vec<_>>(), [0.762, 0.254, 0.254]);
+
+        let soma: f64 = modulos.iter().map(|m| m.largura).sum();
+        assert!(aproximado(soma, itf.largura()));
+        assert!(aproximado(itf.largura(), 112.87));
+    }
+
+    #[test]
+    fn encode_arrecadacao() {
+        let cod_barras = arrecadacao::CodBarras::new(b"81675555555555566667777777777777777777777777").unwrap();
+        let config = Config { largura_estreita: 0.3, proporcao_larga: 2.25, zona_silencio: 6.0 };
+        let itf = Itf::new(&cod_barras, config).unwrap();
+
+        assert!(itf.modulos().iter().all(|m| {
+            aproximado(m.largura, 0.3) || aproximado(m.largura, 0.675) || m.largura == 6.0
+        }));
+        assert!(aproximado(itf.largura(), 101.475 + 12.0));
+    }
+}
@@ -0,0 +1,140 @@
+//! Geração de SVG a partir da codificação ITF, em milímetros.
+
+use std::fmt::Write;
+
+use crate::render::itf::{Itf, ALTURA};
+
+/// Espaço reservado abaixo das barras para a linha digitável, em mm
+pub const ALTURA_LEGENDA: f64 = 6.0;
+/// Tamanho dos caracteres da linha digitável (item 2.3.5), em mm
+pub const TAMANHO_FONTE: f64 = 3.5;
+
+/// Formata em mm com no máximo 3 casas decimais, sem zeros à direita
+fn mm(valor: f64) -> String {
+    let texto = format!("{valor:.3}");
+    texto.trim_end_matches('0').trim_end_matches('.').to_owned()
+}
+
+/// Escapa os caracteres especiais de XML para uso em texto e atributos
+fn escapar(texto: &str) -> String {
+    let mut escapado = String::with_capacity(texto.len());
+
+    for c in texto.chars() {
+        match c {
+            '&' => escapado.push_str("&amp;"),
+            '<' => escapado.push_str("&lt;"),
+            '>' => escapado.push_str("&gt;"),
+            '"' => escapado.push_str("&quot;"),
+            '\'' => escapado.push_str("&apos;"),
+            c => escapado.push(c),
+        }
+    }
+
+    escapado
+}
+
+impl Itf {
+    /// SVG completo no tamanho físico do código de barras, incluindo as
+    /// zonas de silêncio. A `legenda`, quando informada, é escrita abaixo das
+    /// barras ocupando o mesmo comprimento delas, com os caracteres de XML
+    /// escapados.
+    pub fn to_svg(&self, legenda: Option<&str>) -> String {
+        let largura = self.largura();
+        let altura = match legenda {
+            Some(_) => ALTURA + ALTURA_LEGENDA,
+            None => ALTURA,
+        };
+
+        let mut svg = String::new();
+        let _ = write!(
+            svg,
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" version="1.1" "#,
+                r#"width="{l}mm" height="{a}mm" viewBox="0 0 {l} {a}">"#,
+                r#"<rect width="{l}" height="{a}" fill="white"/>"#,
+                r#"<g fill="black">"#,
+            ),
+            l = mm(largura),
+            a = mm(altura),
+        );
+
+        let mut x = 0.0;
+        for modulo in self.modulos() {
+            if modulo.barra {
+                let _ = write!(
+                    svg,
+                    r#"<rect x="{}" width="{}" height="{}"/>"#,
+                    mm(x),
+                    mm(modulo.largura),
+                    mm(ALTURA),
+                );
+            }
+            x += modulo.largura;
+        }
+
+        if let Some(legenda) = legenda {
+            let _ = write!(
+                svg,
+                concat!(
+                    r#"<text x="{x}" y="{y}" font-family="monospace" font-size="{f}" "#,
+                    r#"text-anchor="middle" textLength="{c}" lengthAdjust="spacingAndGlyphs">{t}</text>"#,
+                ),
+                x = mm(largura / 2.0),
+                y = mm(ALTURA + TAMANHO_FONTE + 1.0),
+                f = mm(TAMANHO_FONTE),
+                c = mm(self.config().comprimento()),
+                t = escapar(legenda),
+            );
+        }
+
+        svg.push_str("</g></svg>");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cobranca::CodBarras;
+    use crate::render::itf::Config;
+
+    const COBRANCA: &[u8] = b"10499898100000214032006561000100040099726390";
+
+    #[test]
+    fn format_mm() {
+        assert_eq!(mm(5.0), "5");
+        assert_eq!(mm(102.87), "102.87");
+        assert_eq!(mm(5.254000000000001), "5.254");
+    }
+
+    #[test]
+    fn svg_without_legenda() {
+        let cod_barras = CodBarras::new(COBRANCA).unwrap();
+        let svg = Itf::new(&cod_barras, Config::default()).unwrap().to_svg(None);
+
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" version="1.1" width="112.87mm" height="13mm" viewBox="0 0 112.87 13">"#));
+        assert!(svg.ends_with("</g></svg>"));
+        assert!(!svg.contains("<text"));
+        // Fundo, 2 barras de início, 5 barras por par de dígitos e 2 de fim
+        assert_eq!(svg.matches("<rect").count(), 1 + 2 + 22 * 5 + 2);
+        assert!(svg.contains(r#"<rect x="5" width="0.254" height="13"/><rect x="5.508" width="0.254" height="13"/>"#));
+    }
+
+    #[test]
+    fn svg_with_legenda() {
+        let cod_barras = CodBarras::new(COBRANCA).unwrap();
+        let svg = Itf::new(&cod_barras, Config::default()).unwrap().to_svg(Some("10499.89810 00000.214032"));
+
+        assert!(svg.contains(r#"height="19mm" viewBox="0 0 112.87 19""#));
+        assert!(svg.contains(r#"textLength="102.87" lengthAdjust="spacingAndGlyphs">10499.89810 00000.214032</text>"#));
+    }
+
+    #[test]
+    fn svg_escapes_legenda() {
+        let cod_barras = CodBarras::new(COBRANCA).unwrap();
+        let svg = Itf::new(&cod_barras, Config::default()).unwrap().to_svg(Some(r#"A&B <x> "y" 'z'"#));
+
+        assert!(svg.contains(">A&amp;B &lt;x&gt; &quot;y&quot; &apos;z&apos;</text>"));
+        assert!(!svg.contains("<x>"));
+    }
+}
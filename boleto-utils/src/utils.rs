@@ -1,44 +1,4 @@
-use std::convert::TryInto;
-use lazy_static::lazy_static;
-
-use chrono::{Duration, NaiveDate};
-
-// Fator para o dia 1º de Janeiro de 2010
-const FATOR_VENC_2010: u16 = 4469;
-
-lazy_static! {
-    /// Data base usada até 2025 (1000 == 03/07/2000)
-    static ref CURRENT_BASE_DATE: NaiveDate = NaiveDate::from_ymd_opt(1997, 10, 7).unwrap();
-
-    /// Data base usada de 2025 em diante (1000 == 22/02/2025)
-    static ref NEXT_BASE_DATE: NaiveDate = NaiveDate::from_ymd_opt(2022, 5, 29).unwrap();
-}
-
-pub fn fator_vencimento_to_date(fator: u16) -> Option<NaiveDate> {
-    // Para fatores menores que o limite, calculamos datas do passado (de 2010 em diante)
-    let base = if fator >= FATOR_VENC_2010 {
-        *CURRENT_BASE_DATE
-    } else  {
-        // Para fatores maiores que o limite, calculamos datas do futuro (de 2025 em diante)
-        *NEXT_BASE_DATE
-    };
-
-    if fator >= 1000 {
-        Some(base + Duration::days(fator.into()))
-    } else {
-        None
-    }
-}
-
-
-pub fn date_to_fator_vencimento(date: NaiveDate) -> Option<u16> {
-    let base = if date < NaiveDate::from_ymd_opt(2025, 2, 22).unwrap() {
-        *CURRENT_BASE_DATE
-    } else {
-        *NEXT_BASE_DATE
-    };
-    (date - base).num_days().try_into().ok()
-}
+use core::convert::TryInto;
 
 pub fn u8_array_to_u16(slice: &[u8]) -> u16 {
     slice.iter()
@@ -50,6 +10,15 @@ pub fn u8_array_to_u16(slice: &[u8]) -> u16 {
         .sum()
 }
 
+/// Escreve `valor` em `destino` como dígitos ASCII, completando com zeros à
+/// esquerda. Os dígitos que não couberem são descartados.
+pub fn escrever_digitos(destino: &mut [u8], mut valor: u64) {
+    for digito in destino.iter_mut().rev() {
+        *digito = b'0' + (valor % 10) as u8;
+        valor /= 10;
+    }
+}
+
 pub mod dv_utils {
     pub fn mod_10<'a, I>(values: I) -> u8
     where
@@ -84,80 +53,39 @@ pub mod dv_utils {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use chrono::NaiveDate;
-
-    use crate::utils::{date_to_fator_vencimento, fator_vencimento_to_date, u8_array_to_u16, FATOR_VENC_2010};
-    use crate::utils::dv_utils::{mod_10, mod_11};
-
-    #[test]
-    fn convert_fator_vencimento_to_naive_date_correctly() {
-        assert_eq!(
-            fator_vencimento_to_date(FATOR_VENC_2010),
-            Some(NaiveDate::from_ymd_opt(2010, 1, 1).unwrap()),
-        );
-        assert_eq!(
-            fator_vencimento_to_date(FATOR_VENC_2010 + 1),
-            Some(NaiveDate::from_ymd_opt(2010, 1, 2).unwrap()),
-        );
-        assert_eq!(
-            fator_vencimento_to_date(FATOR_VENC_2010 - 1),
-            Some(NaiveDate::from_ymd_opt(2034, 8, 22).unwrap()),
-        );
-        assert_eq!(
-            fator_vencimento_to_date(4789),
-            Some(NaiveDate::from_ymd_opt(2010, 11, 17).unwrap()),
-        );
-        assert_eq!(
-            fator_vencimento_to_date(9999),
-            Some(NaiveDate::from_ymd_opt(2025, 2, 21).unwrap()),
-        );
-        assert_eq!(
-            fator_vencimento_to_date(1000),
-            Some(NaiveDate::from_ymd_opt(2025, 2, 22).unwrap()),
-        );
-        assert_eq!(
-            fator_vencimento_to_date(1002),
-            Some(NaiveDate::from_ymd_opt(2025, 2, 24).unwrap()),
-        );
-        assert_eq!(
-            fator_vencimento_to_date(1667),
-            Some(NaiveDate::from_ymd_opt(2026, 12, 21).unwrap()),
-        );
+#[cfg(feature = "serde")]
+pub mod serde_utils {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer};
+
+    /// Distingue campo ausente (`None`) de campo `null` (`Some(None)`) em
+    /// campos `Option<Option<T>>`
+    pub fn presente<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+        where
+            D: Deserializer<'de>,
+            T: Deserialize<'de> {
+        T::deserialize(deserializer).map(Some)
     }
 
-    #[test]
-    fn convert_naive_date_to_fator_vencimento_correctly() {
-        assert_eq!(
-            date_to_fator_vencimento(NaiveDate::from_ymd_opt(2010, 1, 2).unwrap()),
-            Some(FATOR_VENC_2010 + 1),
-        );
-        assert_eq!(
-            date_to_fator_vencimento(NaiveDate::from_ymd_opt(2034, 8, 22).unwrap()),
-            Some(FATOR_VENC_2010 - 1),
-        );
-        assert_eq!(
-            date_to_fator_vencimento(NaiveDate::from_ymd_opt(2010, 11, 17).unwrap()),
-            Some(4789),
-        );
-        assert_eq!(
-            date_to_fator_vencimento(NaiveDate::from_ymd_opt(2025, 2, 21).unwrap()),
-            Some(9999),
-        );
-        assert_eq!(
-            date_to_fator_vencimento(NaiveDate::from_ymd_opt(2025, 2, 22).unwrap()),
-            Some(1000),
-        );
-        assert_eq!(
-            date_to_fator_vencimento(NaiveDate::from_ymd_opt(2025, 2, 24).unwrap()),
-            Some(1002),
-        );
-        assert_eq!(
-            date_to_fator_vencimento(NaiveDate::from_ymd_opt(2026, 12, 21).unwrap()),
-            Some(1667),
-        );
+    /// Confere um campo informado na entrada com o valor calculado a partir
+    /// do código de barras. Campos ausentes não são conferidos.
+    pub fn conferir<T, E>(campo: &str, informado: Option<T>, calculado: T) -> Result<(), E>
+        where
+            T: PartialEq,
+            E: Error {
+        match informado {
+            Some(informado) if informado != calculado => Err(E::custom(format_args!(
+                "campo `{campo}` não corresponde ao código de barras"
+            ))),
+            _ => Ok(()),
+        }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::u8_array_to_u16;
+    use crate::utils::dv_utils::{mod_10, mod_11};
 
     #[test]
     fn calculate_mod_10_correctly() {
@@ -198,3 +126,4 @@ mod tests {
         assert_eq!(u8_array_to_u16(b"9999"), 9999);
     }
 }
+
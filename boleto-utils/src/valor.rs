@@ -0,0 +1,232 @@
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::BoletoError;
+
+/// Valor monetário em Reais, armazenado em centavos para evitar erros de
+/// arredondamento de ponto flutuante
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Valor(u64);
+
+impl Valor {
+    pub const ZERO: Valor = Valor(0);
+
+    pub const fn from_centavos(centavos: u64) -> Self {
+        Self(centavos)
+    }
+
+    pub const fn centavos(&self) -> u64 {
+        self.0
+    }
+
+    /// Parte inteira do valor (Reais)
+    pub const fn reais(&self) -> u64 {
+        self.0 / 100
+    }
+
+    pub const fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Converte uma sequência de dígitos ASCII (com os dois últimos sendo os
+    /// centavos) em valor
+    pub(crate) fn from_digits(digits: &[u8]) -> Result<Self, BoletoError> {
+        if digits.is_empty() || !digits.iter().all(|c| c.is_ascii_digit()) {
+            return Err(BoletoError::NumbersOnly);
+        }
+
+        digits
+            .iter()
+            .try_fold(0_u64, |acc, c| acc.checked_mul(10)?.checked_add((c - b'0') as u64))
+            .map(Self)
+            .ok_or(BoletoError::InvalidValor)
+    }
+}
+
+impl From<Valor> for u64 {
+    fn from(valor: Valor) -> u64 {
+        valor.0
+    }
+}
+
+/// Formata o valor em Reais: `R$ 1.234,56`
+impl fmt::Display for Valor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Dígitos dos Reais preenchidos da direita para a esquerda
+        let mut digitos = [0u8; 20];
+        let mut inicio = digitos.len();
+        let mut reais = self.reais();
+
+        loop {
+            inicio -= 1;
+            digitos[inicio] = b'0' + (reais % 10) as u8;
+            reais /= 10;
+
+            if reais == 0 {
+                break;
+            }
+        }
+
+        let reais = &digitos[inicio..];
+
+        f.write_str("R$ ")?;
+
+        for (i, c) in reais.iter().enumerate() {
+            if i > 0 && (reais.len() - i) % 3 == 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{}", *c as char)?;
+        }
+
+        write!(f, ",{:02}", self.0 % 100)
+    }
+}
+
+/// Aceita valores decimais com ponto ou vírgula e até duas casas decimais,
+/// sem separador de milhar (ex.: `1234.56`, `1234,5` ou `1234`)
+impl FromStr for Valor {
+    type Err = BoletoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (reais, centavos) = match s.split_once(['.', ',']) {
+            Some((reais, centavos)) => (reais, centavos),
+            None => (s, ""),
+        };
+
+        if reais.is_empty() || centavos.len() > 2 {
+            return Err(BoletoError::InvalidValor);
+        }
+
+        // Centavos completados com zeros à direita
+        let mut digitos_centavos = [b'0'; 2];
+        digitos_centavos[..centavos.len()].copy_from_slice(centavos.as_bytes());
+
+        let reais = Self::from_digits(reais.as_bytes()).map_err(|_| BoletoError::InvalidValor)?;
+        let centavos = Self::from_digits(&digitos_centavos).map_err(|_| BoletoError::InvalidValor)?;
+
+        reais.0
+            .checked_mul(100)
+            .and_then(|reais| reais.checked_add(centavos.0))
+            .map(Self)
+            .ok_or(BoletoError::InvalidValor)
+    }
+}
+
+/// Serializa como string decimal (ex.: `"1234.56"`)
+#[cfg(feature = "serde")]
+impl Serialize for Valor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer {
+        serializer.collect_str(&format_args!("{}.{:02}", self.reais(), self.0 % 100))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Valor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de> {
+        let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl From<Valor> for rust_decimal::Decimal {
+    fn from(valor: Valor) -> Self {
+        rust_decimal::Decimal::from_i128_with_scale(valor.0 as i128, 2)
+    }
+}
+
+/// Falha para valores negativos, com mais de duas casas decimais significativas
+/// ou que não cabem em `u64` centavos
+#[cfg(feature = "rust_decimal")]
+impl TryFrom<rust_decimal::Decimal> for Valor {
+    type Error = BoletoError;
+
+    fn try_from(value: rust_decimal::Decimal) -> Result<Self, Self::Error> {
+        let centavos = value
+            .checked_mul(rust_decimal::Decimal::ONE_HUNDRED)
+            .ok_or(BoletoError::InvalidValor)?;
+
+        if value.is_sign_negative() || !centavos.fract().is_zero() {
+            return Err(BoletoError::InvalidValor);
+        }
+
+        u64::try_from(centavos)
+            .map(Self)
+            .map_err(|_| BoletoError::InvalidValor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_valor_correctly() {
+        let cases = [
+            (0_u64, "R$ 0,00"),
+            (29, "R$ 0,29"),
+            (100, "R$ 1,00"),
+            (123456, "R$ 1.234,56"),
+            (99999999999, "R$ 999.999.999,99"),
+            (100000000, "R$ 1.000.000,00"),
+        ];
+
+        for (centavos, expected) in cases {
+            assert_eq!(Valor::from_centavos(centavos).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn parse_valor_correctly() {
+        let cases = [
+            ("0.29", 29_u64),
+            ("1234,56", 123456),
+            ("1234.5", 123450),
+            ("1234", 123400),
+            ("00.01", 1),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<Valor>().unwrap().centavos(), expected);
+        }
+
+        for input in ["", ".50", "1.234,56", "1.234", "-1", "1,2,3", "R$ 1,00", "184467440737095516.16"] {
+            assert!(matches!(input.parse::<Valor>(), Err(BoletoError::InvalidValor)), "{input}");
+        }
+    }
+
+    #[test]
+    fn from_digits_correctly() {
+        assert_eq!(Valor::from_digits(b"0000000029").unwrap(), Valor::from_centavos(29));
+        assert_eq!(Valor::from_digits(b"99999999999").unwrap(), Valor::from_centavos(99_999_999_999));
+        assert!(matches!(Valor::from_digits(b"12a"), Err(BoletoError::NumbersOnly)));
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn convert_rust_decimal_correctly() {
+        use rust_decimal::Decimal;
+
+        let valor = Valor::from_centavos(123456);
+        let decimal = Decimal::from(valor);
+
+        assert_eq!(decimal.to_string(), "1234.56");
+        assert_eq!(Valor::try_from(decimal).unwrap(), valor);
+        assert_eq!(Valor::try_from(Decimal::new(29, 2)).unwrap(), Valor::from_centavos(29));
+        assert_eq!(Valor::try_from(Decimal::new(2900, 4)).unwrap(), Valor::from_centavos(29));
+
+        assert!(Valor::try_from(Decimal::new(-1, 2)).is_err());
+        assert!(Valor::try_from(Decimal::new(1, 3)).is_err());
+        assert!(Valor::try_from(Decimal::new(123_456_789, 5)).is_err());
+        assert!(Valor::try_from(Decimal::MAX).is_err());
+        assert!(Valor::try_from(Decimal::MIN).is_err());
+    }
+}
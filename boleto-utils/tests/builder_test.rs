@@ -1,7 +1,11 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
+    use boleto_utils::BoletoError;
+    use boleto_utils::arrecadacao::{Arrecadacao, Convenio, Segmento, TipoValor};
+    use boleto_utils::campo_livre::{CampoLivre, Itau};
     use boleto_utils::cobranca::{Cobranca, CodigoMoeda, CodBanco};
-    use chrono::NaiveDate;
+    use boleto_utils::valor::Valor;
+    use boleto_utils::fator_vencimento::{Data, DataCivil};
 
     #[test]
     fn basic_functionality() {
@@ -10,13 +14,248 @@ mod test {
         let result = builder
             .cod_moeda(CodigoMoeda::Real)
             .cod_banco(CodBanco(301))
-            .valor(99999999.99)
-            .data_vencimento(NaiveDate::from_ymd_opt(2023, 7, 29).unwrap())
-            .build();
+            .valor(Valor::from_centavos(9_999_999_999))
+            .data_vencimento(Data::new(2023, 7, 29).unwrap())
+            .build()
+            .unwrap();
 
         // Erro proposital para ler o output no terminal
         // panic!("{:?}", result.cod_barras);
 
         assert_eq!(result.cod_barras.as_bytes(), b"30198942699999999990000000000000000000000000");
     }
+
+    #[test]
+    fn build_without_campo_livre_for_every_bank() {
+        // O campo livre zerado não segue o layout de nenhum banco, mas não
+        // impede a geração do boleto
+        for cod_banco in [1, 33, 104, 237, 341] {
+            let result = Cobranca::builder()
+                .cod_moeda(CodigoMoeda::Real)
+                .cod_banco(CodBanco(cod_banco))
+                .build()
+                .unwrap();
+
+            assert_eq!(&result.cod_barras.as_bytes()[19..], &[b'0'; 25], "{cod_banco}");
+        }
+
+        let santander = Cobranca::builder()
+            .cod_banco(CodBanco(33))
+            .cod_moeda(CodigoMoeda::Real)
+            .build()
+            .unwrap();
+
+        assert!(santander.campo_livre.is_none());
+        assert_eq!(santander.campo_livre_error.unwrap().erro, BoletoError::InvalidCampoLivre);
+    }
+
+    #[test]
+    fn valor_round_trip() {
+        for valor in ["0.29", "0.01", "1234.56", "99999999.99"] {
+            let valor: Valor = valor.parse().unwrap();
+
+            let result = Cobranca::builder()
+                .cod_moeda(CodigoMoeda::Real)
+                .cod_banco(CodBanco(301))
+                .valor(valor)
+                .build()
+                .unwrap();
+
+            assert_eq!(result.valor, Some(valor));
+        }
+    }
+
+    #[test]
+    fn campo_livre() {
+        let result = Cobranca::builder()
+            .cod_moeda(CodigoMoeda::Real)
+            .cod_banco(CodBanco(341))
+            .valor(Valor::from_centavos(12345))
+            .data_vencimento(Data::new(2026, 12, 21).unwrap())
+            .campo_livre(b"1101234567880057123457000")
+            .build()
+            .unwrap();
+
+        assert_eq!(result.cod_barras.as_bytes(), b"34196166700000123451101234567880057123457000");
+
+        let itau = Itau::Padrao {
+            carteira: "110".parse().unwrap(),
+            nosso_numero: "12345678".parse().unwrap(),
+            dac_nosso_numero: 0,
+            agencia: "0057".parse().unwrap(),
+            conta: "12345".parse().unwrap(),
+            dac_agencia_conta: 0,
+        };
+
+        let result = Cobranca::builder()
+            .cod_moeda(CodigoMoeda::Real)
+            .cod_banco(CodBanco(341))
+            .valor(Valor::from_centavos(12345))
+            .data_vencimento(Data::new(2026, 12, 21).unwrap())
+            .encode_campo_livre(&itau)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(result.cod_barras.as_bytes(), b"34196166700000123451101234567880057123457000");
+        assert!(matches!(result.campo_livre, Some(CampoLivre::Itau(Itau::Padrao { dac_nosso_numero: 8, .. }))));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let builder = || Cobranca::builder().cod_moeda(CodigoMoeda::Real);
+
+        assert!(matches!(
+            builder().cod_banco(CodBanco(1000)).build(),
+            Err(BoletoError::InvalidCodBanco),
+        ));
+        assert!(matches!(
+            builder().cod_banco(CodBanco(1)).valor(Valor::from_centavos(10_000_000_000)).build(),
+            Err(BoletoError::ValorOverflow),
+        ));
+
+        // Datas fora da janela em que o fator é lido de volta como a mesma data
+        for data in [(2000, 7, 2), (1990, 1, 1), (2009, 12, 31), (2034, 8, 23), (2040, 1, 1), (2049, 10, 14)] {
+            let data_vencimento = Data::new(data.0, data.1, data.2).unwrap();
+
+            assert!(matches!(
+                builder().cod_banco(CodBanco(1)).data_vencimento(data_vencimento).build(),
+                Err(BoletoError::InvalidDataVencimento),
+            ));
+        }
+
+        for data in [(2010, 1, 1), (2034, 8, 22)] {
+            let data_vencimento = Data::new(data.0, data.1, data.2).unwrap();
+            let cobranca = builder().cod_banco(CodBanco(1)).data_vencimento(data_vencimento).build().unwrap();
+
+            assert_eq!(cobranca.data_vencimento, Some(data_vencimento));
+        }
+
+        for campo_livre in [&b"123"[..], &b"A".repeat(25)] {
+            assert!(matches!(
+                builder().cod_banco(CodBanco(1)).campo_livre(campo_livre).build(),
+                Err(BoletoError::InvalidCampoLivre),
+            ));
+        }
+
+        // Campo livre com DV inválido para o banco não impede a geração
+        let cobranca = builder().cod_banco(CodBanco(341)).campo_livre(b"1101234567870057123457000").build().unwrap();
+
+        assert!(cobranca.campo_livre.is_none());
+        assert_eq!(cobranca.campo_livre_error.unwrap().erro, BoletoError::InvalidDigitoVerificadorCampoLivre);
+    }
+
+    #[test]
+    fn arrecadacao_basic_functionality() {
+        let result = Arrecadacao::builder()
+            .segmento(Segmento::Saneamento)
+            .tipo_valor(TipoValor::ValorReaisMod10)
+            .convenio(Convenio::Outros(1346))
+            .build()
+            .unwrap();
+
+        assert_eq!(result.cod_barras.as_str(), "82690000000000013460000000000000000000000000");
+        assert_eq!(result.nome_convenio, Some("BRK AMBIENTAL ARAGUAIA SANEAMENTO SA"));
+
+        let result = Arrecadacao::builder()
+            .convenio(Convenio::Carne(*b"44602720"))
+            .valor(Valor::from_centavos(12345))
+            .tipo_valor(TipoValor::ValorReaisMod11)
+            .segmento(Segmento::Carnes)
+            .campo_livre(b"123456789")
+            .build()
+            .unwrap();
+
+        assert_eq!(&result.cod_barras[..3], b"868");
+        assert_eq!(&result.cod_barras[4..], b"0000001234544602720000000000000123456789");
+        assert_eq!(result.valor, Some(Valor::from_centavos(12345)));
+        assert!(matches!(result.convenio, Convenio::Carne(cnpj) if &cnpj == b"44602720"));
+    }
+
+    #[test]
+    fn arrecadacao_invalid_input() {
+        let invalid_builders = [
+            Arrecadacao::builder()
+                .segmento(Segmento::Carnes)
+                .tipo_valor(TipoValor::ValorReaisMod10)
+                .convenio(Convenio::Outros(1346)),
+            Arrecadacao::builder()
+                .segmento(Segmento::Saneamento)
+                .tipo_valor(TipoValor::ValorReaisMod10)
+                .convenio(Convenio::Carne(*b"44602720")),
+            Arrecadacao::builder()
+                .segmento(Segmento::Saneamento)
+                .tipo_valor(TipoValor::ValorReaisMod10)
+                .convenio(Convenio::Outros(10000)),
+        ];
+
+        for builder in invalid_builders {
+            assert!(matches!(builder.build(), Err(BoletoError::InvalidConvenio)));
+        }
+
+        let result = Arrecadacao::builder()
+            .segmento(Segmento::Saneamento)
+            .tipo_valor(TipoValor::ValorReaisMod10)
+            .convenio(Convenio::Outros(1346))
+            .campo_livre(&[b'1'; 26])
+            .build();
+
+        assert!(matches!(result, Err(BoletoError::InvalidCampoLivre)));
+
+        let result = Arrecadacao::builder()
+            .segmento(Segmento::Saneamento)
+            .tipo_valor(TipoValor::ValorReaisMod10)
+            .convenio(Convenio::Outros(1346))
+            .valor(Valor::from_centavos(100_000_000_000))
+            .build();
+
+        assert!(matches!(result, Err(BoletoError::ValorOverflow)));
+    }
+
+    #[test]
+    fn cobranca_to_builder() {
+        let original = Cobranca::new(b"34196166700000123451101234567880057123457000").unwrap();
+
+        let result = original.to_builder().build().unwrap();
+
+        assert_eq!(result.cod_barras.as_bytes(), original.cod_barras.as_bytes());
+
+        let result = original
+            .to_builder()
+            .valor(Valor::from_centavos(50000))
+            .data_vencimento(Data::new(2027, 1, 15).unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(result.cod_banco.0, 341);
+        assert_eq!(result.valor, Some(Valor::from_centavos(50000)));
+        assert_eq!(result.data_vencimento, Data::new(2027, 1, 15));
+        assert_eq!(result.cod_barras.campo_livre(), original.cod_barras.campo_livre());
+        assert_ne!(result.linha_digitavel.as_str(), original.linha_digitavel.as_str());
+    }
+
+    #[test]
+    fn arrecadacao_to_builder() {
+        let barcodes = [
+            b"82690000000000013460000000000000000000000000",
+            b"86705555555555566667777777777777777777777777",
+        ];
+
+        for barcode in barcodes {
+            let original = Arrecadacao::new(barcode).unwrap();
+
+            assert_eq!(original.to_builder().build().unwrap().cod_barras.as_str().as_bytes(), barcode);
+        }
+
+        let original = Arrecadacao::new(b"82690000000000013460000000000000000000000000").unwrap();
+        let result = original
+            .to_builder()
+            .valor(Valor::from_centavos(4590))
+            .build()
+            .unwrap();
+
+        assert_eq!(result.valor, Some(Valor::from_centavos(4590)));
+        assert_eq!(result.nome_convenio, original.nome_convenio);
+        assert_eq!(&result.cod_barras[15..], &original.cod_barras[15..]);
+    }
 }
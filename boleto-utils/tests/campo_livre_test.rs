@@ -0,0 +1,106 @@
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use boleto_utils::{Boleto, BoletoError};
+    use boleto_utils::campo_livre::{self, CampoLivre, Decoders, Generico};
+    use boleto_utils::cobranca::{CodBanco, CodBarras};
+
+    fn campo_livre(boleto: Boleto) -> Option<CampoLivre> {
+        let Boleto::Cobranca(cob) = boleto else {
+            panic!("Não é cobrança válida");
+        };
+
+        cob.campo_livre
+    }
+
+    fn campo_livre_error(boleto: Boleto) -> Option<BoletoError> {
+        let Boleto::Cobranca(cob) = boleto else {
+            panic!("Não é cobrança válida");
+        };
+
+        cob.campo_livre_error.map(|diagnostic| diagnostic.erro)
+    }
+
+    fn decode_sicoob(cod_barras: &CodBarras) -> Result<CampoLivre, BoletoError> {
+        let campo_livre = std::str::from_utf8(cod_barras.campo_livre()).unwrap();
+
+        let campos = BTreeMap::from([
+            ("carteira".to_owned(), campo_livre[0..1].to_owned()),
+            ("agencia".to_owned(), campo_livre[1..5].to_owned()),
+            ("modalidade".to_owned(), campo_livre[5..7].to_owned()),
+            ("codigo_cliente".to_owned(), campo_livre[7..14].to_owned()),
+            ("parcela".to_owned(), campo_livre[22..25].to_owned()),
+        ]);
+
+        Ok(CampoLivre::Outro(Generico {
+            nosso_numero: campo_livre[14..22].to_owned(),
+            campos,
+        }))
+    }
+
+    #[test]
+    fn registered_decoder_is_used() {
+        let barcode = b"75696903800002500001434301033723400014933001";
+
+        let mut decoders = Decoders::default();
+        assert!(campo_livre(Boleto::new_with(&decoders, barcode).unwrap()).is_none());
+
+        decoders.registrar(CodBanco(756), decode_sicoob);
+
+        let Some(CampoLivre::Outro(generico)) = campo_livre(Boleto::new_with(&decoders, barcode).unwrap()) else {
+            panic!("Campo livre deveria ter sido decodificado");
+        };
+
+        assert_eq!(generico.nosso_numero, "00014933");
+        assert_eq!(generico.campos["agencia"], "4343");
+        assert_eq!(generico.campos["codigo_cliente"], "0337234");
+
+        // O registro global não é alterado
+        assert!(campo_livre(Boleto::new(barcode).unwrap()).is_none());
+
+        assert!(decoders.remover(CodBanco(756)));
+        assert!(!decoders.remover(CodBanco(756)));
+    }
+
+    #[test]
+    fn registered_decoder_errors_are_reported() {
+        let barcode = b"99996444455555555556666666666666666666666666";
+
+        let mut decoders = Decoders::vazio();
+        decoders.registrar(CodBanco(999), |_: &CodBarras| Err(BoletoError::InvalidCampoLivre));
+
+        assert_eq!(campo_livre_error(Boleto::new_with(&decoders, barcode).unwrap()), Some(BoletoError::InvalidCampoLivre));
+
+        decoders.remover(CodBanco(999));
+
+        assert_eq!(campo_livre_error(Boleto::new_with(&decoders, barcode).unwrap()), None);
+    }
+
+    #[test]
+    fn builtin_decoders_can_be_removed() {
+        let barcode = b"34196166700000123451101234567880057123457000";
+
+        let mut decoders = Decoders::default();
+        assert!(campo_livre(Boleto::new_with(&decoders, barcode).unwrap()).is_some());
+
+        assert!(decoders.remover(CodBanco(341)));
+        assert!(campo_livre(Boleto::new_with(&decoders, barcode).unwrap()).is_none());
+    }
+
+    /// Único teste que altera o registro global. Os demais usam `Decoders`
+    /// próprios e não são afetados.
+    #[test]
+    fn global_registry() {
+        let barcode = b"99996444455555555556666666666666666666666666";
+
+        campo_livre::registrar(CodBanco(999), |_: &CodBarras| Err(BoletoError::InvalidCampoLivre));
+
+        assert_eq!(campo_livre_error(Boleto::new(barcode).unwrap()), Some(BoletoError::InvalidCampoLivre));
+        assert_eq!(campo_livre_error(Boleto::new_with(&Decoders::default(), barcode).unwrap()), None);
+
+        assert!(campo_livre::remover(CodBanco(999)));
+
+        assert_eq!(campo_livre_error(Boleto::new(barcode).unwrap()), None);
+    }
+}
@@ -0,0 +1,52 @@
+// Usa apenas a API disponível sem `std`, então roda com qualquer feature
+#[cfg(test)]
+mod test {
+    use boleto_utils::{BoletoError, CodBarras};
+    use boleto_utils::arrecadacao::{Convenio, Segmento, TipoValor};
+    use boleto_utils::cobranca::{CodBanco, CodigoMoeda};
+    use boleto_utils::fator_vencimento::{Data, DataCivil};
+    use boleto_utils::valor::Valor;
+
+    #[test]
+    fn parse_cobranca() {
+        let cod_barras = b"10499898100000214032006561000100040099726390";
+        let linha_digitavel = b"10492006506100010004200997263900989810000021403";
+
+        for value in [&cod_barras[..], &linha_digitavel[..]] {
+            let CodBarras::Cobranca(parsed) = CodBarras::parse(value).unwrap() else {
+                panic!("Should be Cobranca");
+            };
+
+            assert_eq!(parsed.as_bytes(), cod_barras);
+            assert_eq!(parsed.cod_banco(), CodBanco(104));
+            assert_eq!(parsed.cod_moeda(), Ok(CodigoMoeda::Real));
+            assert_eq!(parsed.data_vencimento(), Data::new(2022, 5, 10));
+            assert_eq!(parsed.valor(), Some(Valor::from_centavos(21403)));
+            assert_eq!(parsed.campo_livre(), b"2006561000100040099726390"[..].as_ref());
+        }
+    }
+
+    #[test]
+    fn parse_arrecadacao() {
+        let CodBarras::Arrecadacao(parsed) = CodBarras::parse(b"81675555555555566667777777777777777777777777").unwrap() else {
+            panic!("Should be Arrecadacao");
+        };
+
+        assert_eq!(parsed.segmento(), Ok(Segmento::Prefeituras));
+        assert_eq!(parsed.tipo_valor(), Ok(TipoValor::ValorReaisMod10));
+        assert!(matches!(parsed.convenio(), Ok(Convenio::Outros(_))));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(CodBarras::parse(b""), Err(BoletoError::InvalidLength));
+        assert_eq!(
+            CodBarras::parse(b"10490898100000214032006561000100040099726390"),
+            Err(BoletoError::InvalidDigitoVerificadorGeral),
+        );
+        assert_eq!(
+            CodBarras::parse(b"10492006516100010004200997263900989810000021403"),
+            Err(BoletoError::InvalidDigitoVerificadorCampos),
+        );
+    }
+}
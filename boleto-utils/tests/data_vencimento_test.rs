@@ -0,0 +1,68 @@
+// Confere, em tempo de compilação, o tipo de `data_vencimento` em cada
+// combinação de features
+#[cfg(test)]
+mod test {
+    const COD_BARRAS: &[u8] = b"10499898100000214032006561000100040099726390";
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn data_vencimento_is_chrono_naive_date() {
+        let esperada = chrono::NaiveDate::from_ymd_opt(2022, 5, 10);
+
+        let cod_barras = boleto_utils::cobranca::CodBarras::new(COD_BARRAS).unwrap();
+        let data_vencimento: Option<chrono::NaiveDate> = cod_barras.data_vencimento();
+        assert_eq!(data_vencimento, esperada);
+
+        #[cfg(feature = "std")]
+        {
+            let cobranca = boleto_utils::cobranca::Cobranca::new(COD_BARRAS).unwrap();
+            let data_vencimento: Option<chrono::NaiveDate> = cobranca.data_vencimento;
+            let possiveis: Vec<chrono::NaiveDate> = cobranca.datas_vencimento_possiveis;
+
+            assert_eq!(data_vencimento, esperada);
+            assert!(possiveis.is_empty());
+        }
+    }
+
+    #[cfg(all(feature = "time", not(feature = "chrono")))]
+    #[test]
+    fn data_vencimento_is_time_date() {
+        let esperada = time::Date::from_calendar_date(2022, time::Month::May, 10).ok();
+
+        let cod_barras = boleto_utils::cobranca::CodBarras::new(COD_BARRAS).unwrap();
+        let data_vencimento: Option<time::Date> = cod_barras.data_vencimento();
+        assert_eq!(data_vencimento, esperada);
+
+        #[cfg(feature = "std")]
+        {
+            let cobranca = boleto_utils::cobranca::Cobranca::new(COD_BARRAS).unwrap();
+            let data_vencimento: Option<time::Date> = cobranca.data_vencimento;
+            let possiveis: Vec<time::Date> = cobranca.datas_vencimento_possiveis;
+
+            assert_eq!(data_vencimento, esperada);
+            assert!(possiveis.is_empty());
+        }
+    }
+
+    #[cfg(not(any(feature = "chrono", feature = "time")))]
+    #[test]
+    fn data_vencimento_is_data() {
+        use boleto_utils::fator_vencimento::{Data, DataCivil};
+
+        let esperada = Data::new(2022, 5, 10);
+
+        let cod_barras = boleto_utils::cobranca::CodBarras::new(COD_BARRAS).unwrap();
+        let data_vencimento: Option<Data> = cod_barras.data_vencimento();
+        assert_eq!(data_vencimento, esperada);
+
+        #[cfg(feature = "std")]
+        {
+            let cobranca = boleto_utils::cobranca::Cobranca::new(COD_BARRAS).unwrap();
+            let data_vencimento: Option<Data> = cobranca.data_vencimento;
+            let possiveis: Vec<Data> = cobranca.datas_vencimento_possiveis;
+
+            assert_eq!(data_vencimento, esperada);
+            assert!(possiveis.is_empty());
+        }
+    }
+}
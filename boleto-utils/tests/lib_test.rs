@@ -1,7 +1,8 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
-    use boleto_utils::{Boleto, BoletoError, cobranca::CodigoMoeda};
-    use chrono::NaiveDate;
+    use boleto_utils::{Boleto, BoletoError, cobranca::CodigoMoeda, valor::Valor};
+    use boleto_utils::campo_livre::{CampoLivre, CodigoBeneficiario};
+    use boleto_utils::fator_vencimento::{Data, DataCivil};
 
     #[test]
     fn invalid_input_error() {
@@ -81,57 +82,57 @@ mod tests {
             (
                 b"11199100055555555556666666666666666666666666",
                 1000,
-                Some(NaiveDate::from_ymd_opt(2025, 2, 22).unwrap()),
+                Some(Data::new(2025, 2, 22).unwrap()),
             ),
             (
                 b"11191100255555555556666666666666666666666666",
                 1002,
-                Some(NaiveDate::from_ymd_opt(2025, 2, 24).unwrap()),
+                Some(Data::new(2025, 2, 24).unwrap()),
             ),
             (
                 b"11196166755555555556666666666666666666666666",
                 1667,
-                Some(NaiveDate::from_ymd_opt(2026, 12, 21).unwrap()),
+                Some(Data::new(2026, 12, 21).unwrap()),
             ),
             (
                 b"11198478955555555556666666666666666666666666",
                 4789,
-                Some(NaiveDate::from_ymd_opt(2010, 11, 17).unwrap()),
+                Some(Data::new(2010, 11, 17).unwrap()),
             ),
             (
                 b"11193999955555555556666666666666666666666666",
                 9999,
-                Some(NaiveDate::from_ymd_opt(2025, 2, 21).unwrap()),
+                Some(Data::new(2025, 2, 21).unwrap()),
             ),
             (
                 b"75696903800002500001434301033723400014933001",
                 9038,
-                Some(NaiveDate::from_ymd_opt(2022, 7, 6).unwrap()),
+                Some(Data::new(2022, 7, 6).unwrap()),
             ),
             (
                 b"00191667900002434790000002656973019362470618",
                 6679,
-                Some(NaiveDate::from_ymd_opt(2016, 1, 20).unwrap()),
+                Some(Data::new(2016, 1, 20).unwrap()),
             ),
             (
                 b"00195586200000773520000002464206011816073018",
                 5862,
-                Some(NaiveDate::from_ymd_opt(2013, 10, 25).unwrap()),
+                Some(Data::new(2013, 10, 25).unwrap()),
             ),
             (
                 b"75592896700003787000003389850761252543475984",
                 8967,
-                Some(NaiveDate::from_ymd_opt(2022, 4, 26).unwrap()),
+                Some(Data::new(2022, 4, 26).unwrap()),
             ),
             (
                 b"23791672000003249052028269705944177105205220",
                 6720,
-                Some(NaiveDate::from_ymd_opt(2016, 3, 1).unwrap()),
+                Some(Data::new(2016, 3, 1).unwrap()),
             ),
             (
                 b"23791672000003097902028060007024617500249000",
                 6720,
-                Some(NaiveDate::from_ymd_opt(2016, 3, 1).unwrap()),
+                Some(Data::new(2016, 3, 1).unwrap()),
             ),
         ];
         for (barcode, expected_fator, expected_date) in barcodes {
@@ -165,11 +166,11 @@ mod tests {
         let barcodes = [
             (
                 b"11191444455555555556666666666666666666666666",
-                Some(55555555.55_f64),
+                Some(Valor::from_centavos(5_555_555_555)),
             ),
             (
                 b"11196444499999999996666666666666666666666666",
-                Some(99999999.99),
+                Some(Valor::from_centavos(9_999_999_999)),
             ),
             (b"11193444400000000006666666666666666666666666", None),
         ];
@@ -205,6 +206,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn campo_livre_validation() {
+        // Campos livres inválidos para o layout do banco, mas com DV geral
+        // válido: o boleto é lido e o problema fica em `campo_livre_error`
+        let invalidos = [
+            // Caixa (SIGCB) com DV do campo livre inválido
+            (b"10497898100000214032006561000100040099726391", BoletoError::InvalidDigitoVerificadorCampoLivre),
+            // Santander sem o "9" fixo
+            (b"03391204600000273718028203356661245780020101", BoletoError::InvalidCampoLivre),
+            // Santander com IOF inválido
+            (b"03395204600000273719028203356661245780025101", BoletoError::InvalidCampoLivre),
+        ];
+
+        for (barcode, erro) in invalidos {
+            let Boleto::Cobranca(cob) = Boleto::new(barcode).unwrap() else {
+                panic!("Não é cobrança válida");
+            };
+
+            assert!(cob.campo_livre.is_none());
+
+            let diagnostic = cob.campo_livre_error.unwrap();
+            assert_eq!(diagnostic.erro, erro);
+            assert_eq!(diagnostic.posicao, 19..44);
+        }
+
+        let boleto = Boleto::new(b"03398204600000273719028203356661245780020101").unwrap();
+
+        let Boleto::Cobranca(cob) = boleto else {
+            panic!("Não é cobrança válida");
+        };
+
+        assert_eq!(cob.campo_livre.unwrap().nosso_numero(), "5666124578002");
+    }
+
+    #[test]
+    fn campo_livre_caixa_codigo_sete_digitos() {
+        let boleto = Boleto::new(b"10493989800000214031234567000100040000123450").unwrap();
+
+        let Boleto::Cobranca(cob) = boleto else {
+            panic!("Não é cobrança válida");
+        };
+
+        let Some(CampoLivre::Caixa(caixa)) = cob.campo_livre else {
+            panic!("Deveria ser campo livre da Caixa");
+        };
+
+        assert!(matches!(caixa.codigo_beneficiario, CodigoBeneficiario::SeteDigitos { .. }));
+        assert_eq!(caixa.codigo_beneficiario.as_str(), "1234567");
+        assert_eq!(caixa.nosso_numero.as_str(), "14000000000012345");
+
+        // "1100009": o 7º dígito é o DV de "110000", então o layout não pode
+        // ser determinado e o campo livre não é decodificado
+        let boleto = Boleto::new(b"10491989800000214031100009000100040000123458").unwrap();
+
+        let Boleto::Cobranca(cob) = boleto else {
+            panic!("Não é cobrança válida");
+        };
+
+        assert!(cob.campo_livre.is_none());
+        assert!(cob.campo_livre_error.is_none());
+    }
+
     #[test]
     fn invalid_length_error() {
         let invalid_lengths = [1, 10, 20, 30, 40, 43, 46, 45, 49, 50];
@@ -216,4 +279,149 @@ mod tests {
             assert!(matches!(result, Err(BoletoError::InvalidLength)));
        }
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_linha_digitavel_formatada() {
+        let cases = [
+            (
+                "75691434360103372340200149330011690380000250000",
+                "75691.43436 01033.723402 00149.330011 6 90380000250000",
+            ),
+            (
+                "836555555553555566667773777777777775777777777775",
+                "83655555555-3 55556666777-3 77777777777-5 77777777777-5",
+            ),
+        ];
+
+        for (linha_digitavel, expected) in cases {
+            let json = serde_json::to_value(Boleto::new(linha_digitavel.as_bytes()).unwrap()).unwrap();
+
+            assert_eq!(json["dados"]["linha_digitavel"], linha_digitavel);
+            assert_eq!(json["dados"]["linha_digitavel_formatada"], expected);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_round_trip() {
+        let cases = [
+            "34196166700000123451101234567880057123457000",
+            "11191444455555555556666666666666666666666666",
+            "30195917700001452780000000002310237287225104",
+            "75691434360103372340200149330011690380000250000",
+            "868900000015238626752850720221223001810200058809",
+            "836555555553555566667773777777777775777777777775",
+            // Campo livre inválido para o layout do Santander
+            "03395204600000273719028203356661245780025101",
+        ];
+
+        for input in cases {
+            let json = serde_json::to_value(Boleto::new(input.as_bytes()).unwrap()).unwrap();
+            let boleto: Boleto = serde_json::from_value(json.clone()).unwrap();
+
+            assert_eq!(serde_json::to_value(&boleto).unwrap(), json, "{input}");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_bare_string() {
+        let boleto: Boleto = serde_json::from_str(r#""75691434360103372340200149330011690380000250000""#).unwrap();
+        assert!(matches!(boleto, Boleto::Cobranca(c) if c.cod_barras.as_str() == "75696903800002500001434301033723400014933001"));
+
+        let boleto: Boleto = serde_json::from_str(r#""86890000001238626752857202212230081020005880""#).unwrap();
+        assert!(matches!(boleto, Boleto::Arrecadacao(_)));
+
+        let boleto: Boleto = serde_json::from_str(r#"{"tipo": "cobranca", "dados": "75696903800002500001434301033723400014933001"}"#).unwrap();
+        assert!(matches!(boleto, Boleto::Cobranca(_)));
+
+        assert!(serde_json::from_str::<Boleto>(r#""75696903800002500001434301033723400014933002""#).is_err());
+
+        // Mesmas regras da forma serializada, que também não aceita a linha
+        // digitável formatada
+        let formatada = r#""75691.43436 01033.723402 00149.330011 6 90380000250000""#;
+        assert!(serde_json::from_str::<Boleto>(formatada).is_err());
+        assert!(serde_json::from_str::<Boleto>(&format!(r#"{{"tipo": "cobranca", "dados": {formatada}}}"#)).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_rejects_mismatched_fields() {
+        let json = serde_json::to_value(Boleto::new(b"34196166700000123451101234567880057123457000").unwrap()).unwrap();
+
+        let alteracoes = [
+            ("/dados/valor", serde_json::json!("123.46")),
+            ("/dados/digito_verificador", serde_json::json!(7)),
+            ("/dados/fator_vencimento", serde_json::json!(6168)),
+            ("/dados/cod_moeda", serde_json::json!("Outras")),
+            ("/dados/data_vencimento", serde_json::json!("2015-01-01")),
+            ("/dados/data_vencimento", serde_json::Value::Null),
+            ("/dados/campo_livre/nosso_numero", serde_json::json!("12345679")),
+            ("/dados/linha_digitavel", serde_json::json!("34191101213456788005571234570009616670000012345")),
+            ("/tipo", serde_json::json!("arrecadacao")),
+        ];
+
+        for (campo, valor) in alteracoes {
+            let mut alterado = json.clone();
+            *alterado.pointer_mut(campo).unwrap() = valor;
+
+            assert!(serde_json::from_value::<Boleto>(alterado).is_err(), "{campo}");
+        }
+
+        let json = serde_json::to_value(Boleto::new(b"868900000015238626752850720221223001810200058809").unwrap()).unwrap();
+
+        for (campo, valor) in [("/dados/segmento", "Saneamento"), ("/dados/nome_convenio", "CEMIG")] {
+            let mut alterado = json.clone();
+            *alterado.pointer_mut(campo).unwrap() = serde_json::json!(valor);
+
+            assert!(serde_json::from_value::<Boleto>(alterado).is_err(), "{campo}");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_data_vencimento_from_other_policy() {
+        // Fator 1667 corresponde a 2026-12-21 pela política padrão e a 2002-05-01
+        // no ciclo anterior
+        let mut json = serde_json::to_value(Boleto::new(b"34196166700000123451101234567880057123457000").unwrap()).unwrap();
+        json["dados"]["data_vencimento"] = serde_json::json!("2002-05-01");
+
+        let boleto: Boleto = serde_json::from_value(json).unwrap();
+
+        assert!(matches!(
+            boleto,
+            Boleto::Cobranca(c) if c.data_vencimento == Data::new(2002, 5, 1)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_rejects_datas_vencimento_possiveis_from_other_fator() {
+        let json = serde_json::to_value(Boleto::new(b"34196166700000123451101234567880057123457000").unwrap()).unwrap();
+
+        let mut ambiguo = json.clone();
+        ambiguo["dados"]["datas_vencimento_possiveis"] = serde_json::json!(["2002-05-01", "2026-12-21"]);
+        assert!(serde_json::from_value::<Boleto>(ambiguo).is_ok());
+
+        let invalidas = [
+            serde_json::json!(["2026-12-21"]),
+            serde_json::json!(["2026-12-21", "2002-05-01"]),
+            serde_json::json!(["2002-05-01", "2026-12-20"]),
+            serde_json::json!(["2002-05-01", "2051-08-08"]),
+            serde_json::json!(["2002-05-01", "2026-12-21", "2026-12-21"]),
+        ];
+
+        for datas in invalidas {
+            let mut alterado = json.clone();
+            alterado["dados"]["datas_vencimento_possiveis"] = datas.clone();
+
+            assert!(serde_json::from_value::<Boleto>(alterado).is_err(), "{datas}");
+        }
+
+        let mut sem_data = json.clone();
+        sem_data["dados"].as_object_mut().unwrap().remove("data_vencimento");
+        sem_data["dados"]["datas_vencimento_possiveis"] = serde_json::json!(["2002-05-01", "2026-12-21"]);
+        assert!(serde_json::from_value::<Boleto>(sem_data).is_err());
+    }
 }
\ No newline at end of file